@@ -0,0 +1,84 @@
+use crate::color::{lose, win};
+use crate::lang::{t, tf};
+use crate::layout;
+use crate::{clear_screen, pause, print_heading, read_name, run_match};
+use rps_game::{
+    challenge_seed, check_match_winner, computer_name, unix_now, utc_date, Defaults, Difficulty,
+    GameConfig, MatchFormat, MatchState, RoundWinner, Scoreboard,
+};
+
+const NAME_WIDTH: usize = 20;
+
+pub fn daily_challenge(defaults: &Defaults, scoreboard: &mut Scoreboard) {
+    // Fixed when the challenge starts, so a match played over midnight still
+    // counts for the day it began.
+    let date = utc_date(unix_now());
+
+    clear_screen();
+    print_heading(&tf("challenge.title", &[&date]));
+    println!("{}\n", t("challenge.about"));
+
+    let player1 = read_name(t("challenge.name"), defaults.player1.as_deref(), None);
+    if scoreboard.challenge_result(&date, &player1).is_some() {
+        println!("\n{}", t("challenge.played"));
+        print_leaderboard(scoreboard, &date);
+        pause();
+        return;
+    }
+
+    let config = GameConfig {
+        player1,
+        player2: computer_name(Difficulty::Hard, true),
+        format: MatchFormat::FirstToK(5),
+        difficulty: Some(Difficulty::Hard),
+        rng_seed: Some(challenge_seed(&date)),
+        use_color: defaults.use_color.unwrap_or(true),
+        show_ascii: defaults.show_ascii.unwrap_or(true),
+        show_emoji: defaults.show_emoji.unwrap_or(false),
+        ..Default::default()
+    };
+    let mut state = MatchState::new(config);
+    state.in_challenge = true;
+
+    run_match(&mut state, scoreboard);
+
+    clear_screen();
+    print_heading(&tf("challenge.title", &[&date]));
+    match check_match_winner(&state) {
+        Some(winner) => {
+            scoreboard.record_challenge(&date, &state, winner);
+            scoreboard.save();
+        }
+        None => println!("{}", t("challenge.left")),
+    }
+    print_leaderboard(scoreboard, &date);
+    pause();
+}
+
+fn print_leaderboard(scoreboard: &Scoreboard, date: &str) {
+    let results = scoreboard.challenge_leaderboard(date);
+    if results.is_empty() {
+        println!("\n{}", t("challenge.nobody"));
+        return;
+    }
+    println!("\n{}\n", t("challenge.leaderboard"));
+    let name_width = layout::name_width(30, NAME_WIDTH);
+    for (pos, r) in results.iter().enumerate() {
+        let line = match r.winner {
+            RoundWinner::Player1 => win(&tf(
+                "challenge.won",
+                &[&r.rounds_won, &r.rounds_lost, &r.rounds_played],
+            )),
+            _ => lose(&tf(
+                "challenge.lost",
+                &[&r.rounds_won, &r.rounds_lost, &r.rounds_played],
+            )),
+        };
+        println!(
+            "{:<4} {:<name_width$} {}",
+            pos + 1,
+            layout::fit(&r.player, name_width),
+            line
+        );
+    }
+}
@@ -0,0 +1,303 @@
+use rps_game::{
+    check_ruleset, computer_name, seed_from_env, CustomRuleset, Difficulty, GameConfig,
+    MatchFormat, Mode, Ruleset,
+};
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: rps [options]
+
+With no options the interactive menu starts. Otherwise a match starts
+straight away with these settings:
+
+  --p1 NAME              Player 1 (required)
+  --p2 NAME              Player 2; implies multiplayer
+  --mode MODE            single, multi or network; defaults to multi when
+                         --p2 is given
+  --ruleset RULES        classic (default), extended, rps:N for the balanced
+                         game with N moves (N odd), or a custom ruleset file
+  --format FORMAT        single (default), bestof:N, firstto:K,
+                         deuce:K (first to K, win by 2) or points:T
+  --difficulty LEVEL     easy (default), normal, hard, expert, adaptive or
+                         mirror; single-player
+  --timer SECONDS        Time limit per move; single-player
+  --ai-hints             Explain after each round what the computer
+                         predicted; Hard difficulty
+  --replay-ties          Play tied rounds again instead of counting them
+  --commit               Show a hash of each move before the reveal;
+                         multiplayer
+  --shared-screen        Type both moves on one screen and reveal them
+                         together; multiplayer
+  --host [IP]:PORT       Host a network match; the other player is whoever
+                         connects
+  --connect IP:PORT      Join a network match; the host picks the ruleset
+                         and format, so only --p1 and display options apply
+  --event-log FILE       Append each round to FILE as a line of JSON
+  --no-color             Disable colors
+  --no-ascii             Disable ASCII art
+  --emoji                Show moves as emoji instead of ASCII art
+  --no-animation         Skip the countdown animation
+  --no-clear             Never clear the screen; a separator line is printed
+                         instead, as it is whenever output isn't a terminal
+  --script FILE          Read moves from FILE, one per line, before asking
+                         at the keyboard; works with the menu too, where a
+                         save that can't be loaded then exits with an error
+  --resume SLOT          Continue saved game SLOT, or exit with an error if
+                         it can't be loaded; only display options go with it";
+
+// Only flags that configure a match; --no-animation and --no-clear are read
+// where they're used.
+const GAME_FLAGS: [&str; 17] = [
+    "--p1",
+    "--p2",
+    "--mode",
+    "--ruleset",
+    "--format",
+    "--difficulty",
+    "--timer",
+    "--commit",
+    "--shared-screen",
+    "--no-color",
+    "--no-ascii",
+    "--emoji",
+    "--host",
+    "--connect",
+    "--event-log",
+    "--ai-hints",
+    "--replay-ties",
+];
+
+pub enum Launch {
+    Menu,
+    Resume(u32),
+    Match(GameConfig),
+    Host(GameConfig, String),
+    // Only the name and display settings of the config are used.
+    Connect(GameConfig, String),
+}
+
+// Taken out before parse_args, since it applies to menu and match launches
+// alike.
+pub fn take_script(args: &mut Vec<String>) -> Result<Option<String>, String> {
+    let Some(i) = args.iter().position(|a| a == "--script") else {
+        return Ok(None);
+    };
+    if i + 1 >= args.len() {
+        return Err("--script needs a value".to_string());
+    }
+    let path = args.remove(i + 1);
+    args.remove(i);
+    Ok(Some(path))
+}
+
+pub fn parse_args(args: &[String]) -> Result<Launch, String> {
+    let display_only = ["--no-animation", "--no-clear"];
+    if let Some(i) = args.iter().position(|a| a == "--resume") {
+        let value = args.get(i + 1).ok_or("--resume needs a value")?;
+        let slot = value
+            .parse::<u32>()
+            .map_err(|_| format!("invalid slot {}", value))?;
+        let mut rest = args
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i && j != i + 1);
+        if let Some((_, other)) = rest.find(|(_, a)| !display_only.contains(&a.as_str())) {
+            return Err(format!("{} can't be used with --resume", other));
+        }
+        return Ok(Launch::Resume(slot));
+    }
+    if !args.iter().any(|a| GAME_FLAGS.contains(&a.as_str())) {
+        if let Some(unknown) = args.iter().find(|a| !display_only.contains(&a.as_str())) {
+            return Err(format!("unknown option {}", unknown));
+        }
+        return Ok(Launch::Menu);
+    }
+
+    let mut player1 = None;
+    let mut player2 = None;
+    let mut mode = None;
+    let mut ruleset = Ruleset::Classic;
+    let mut format = MatchFormat::SingleRound;
+    let mut difficulty = None;
+    let mut timer = None;
+    let mut use_color = true;
+    let mut show_ascii = true;
+    let mut show_emoji = false;
+    let mut commit_moves = false;
+    let mut shared_screen = false;
+    let mut host = None;
+    let mut connect = None;
+    let mut event_log = None;
+    let mut show_ai_hints = false;
+    let mut replay_ties = false;
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = || {
+            it.next()
+                .cloned()
+                .ok_or_else(|| format!("{} needs a value", arg))
+        };
+        match arg.as_str() {
+            "--p1" => player1 = Some(value()?),
+            "--p2" => player2 = Some(value()?),
+            "--mode" => {
+                mode = Some(match value()?.as_str() {
+                    "single" => Mode::SinglePlayer,
+                    "multi" => Mode::Multiplayer,
+                    "network" => Mode::Network,
+                    other => return Err(format!("unknown mode {}", other)),
+                })
+            }
+            "--ruleset" => ruleset = parse_ruleset(&value()?)?,
+            "--format" => format = parse_format(&value()?)?,
+            "--difficulty" => difficulty = Some(parse_difficulty(&value()?)?),
+            "--timer" => {
+                let v = value()?;
+                match v.parse::<u64>() {
+                    Ok(secs) if secs >= 1 => timer = Some(secs),
+                    _ => return Err(format!("invalid timer {}", v)),
+                }
+            }
+            "--no-color" => use_color = false,
+            "--no-ascii" => show_ascii = false,
+            "--emoji" => show_emoji = true,
+            "--commit" => commit_moves = true,
+            "--shared-screen" => shared_screen = true,
+            "--ai-hints" => show_ai_hints = true,
+            "--replay-ties" => replay_ties = true,
+            "--host" => host = Some(value()?),
+            "--connect" => connect = Some(value()?),
+            "--event-log" => event_log = Some(PathBuf::from(value()?)),
+            "--no-animation" | "--no-clear" => {}
+            other => return Err(format!("unknown option {}", other)),
+        }
+    }
+
+    let player1 = player1
+        .filter(|p| !p.is_empty())
+        .ok_or("--p1 is required")?;
+    if host.is_some() && connect.is_some() {
+        return Err("--host and --connect can't be used together".to_string());
+    }
+    let networked = host.is_some() || connect.is_some();
+    let mode = mode.unwrap_or(if networked {
+        Mode::Network
+    } else if player2.is_some() {
+        Mode::Multiplayer
+    } else {
+        Mode::SinglePlayer
+    });
+    if (mode == Mode::Network) != networked {
+        return Err("network mode goes with --host or --connect".to_string());
+    }
+    if connect.is_some() {
+        let set_by_host = ["--p2", "--mode", "--ruleset", "--format"];
+        if let Some(flag) = args.iter().find(|a| set_by_host.contains(&a.as_str())) {
+            return Err(format!("{} is set by the host", flag));
+        }
+    }
+
+    let player2 = match mode {
+        Mode::SinglePlayer => {
+            if player2.is_some() {
+                return Err("--p2 can't be used in single-player".to_string());
+            }
+            computer_name(difficulty.unwrap_or(Difficulty::Easy), true)
+        }
+        Mode::Multiplayer | Mode::Network if difficulty.is_some() || timer.is_some() => {
+            return Err("--difficulty and --timer are single-player only".to_string());
+        }
+        // Filled in with the name of whoever connects.
+        Mode::Network => {
+            if player2.is_some() {
+                return Err("--p2 can't be used in network mode".to_string());
+            }
+            String::new()
+        }
+        Mode::Multiplayer => match player2 {
+            Some(p) if !p.is_empty() && p != player1 => p,
+            Some(_) => return Err("--p2 must be different from --p1".to_string()),
+            None => return Err("--p2 is required in multiplayer".to_string()),
+        },
+    };
+    if commit_moves && mode != Mode::Multiplayer {
+        return Err("--commit is multiplayer only".to_string());
+    }
+    if shared_screen && mode != Mode::Multiplayer {
+        return Err("--shared-screen is multiplayer only".to_string());
+    }
+    let difficulty = match mode {
+        Mode::SinglePlayer => Some(difficulty.unwrap_or(Difficulty::Easy)),
+        Mode::Multiplayer | Mode::Network => None,
+    };
+
+    let config = GameConfig {
+        player1,
+        player2,
+        mode,
+        ruleset,
+        format,
+        difficulty,
+        rng_seed: seed_from_env(),
+        use_color,
+        show_ascii: show_ascii && !show_emoji,
+        show_emoji,
+        move_timeout_secs: timer,
+        commit_moves,
+        event_log,
+        show_ai_hints,
+        replay_ties,
+        shared_screen,
+        ..Default::default()
+    };
+    Ok(match (host, connect) {
+        (Some(addr), _) => Launch::Host(config, addr),
+        (_, Some(addr)) => Launch::Connect(config, addr),
+        _ => Launch::Match(config),
+    })
+}
+
+pub fn parse_difficulty(s: &str) -> Result<Difficulty, String> {
+    match s {
+        "easy" => Ok(Difficulty::Easy),
+        "normal" => Ok(Difficulty::Normal),
+        "hard" => Ok(Difficulty::Hard),
+        "expert" => Ok(Difficulty::Expert),
+        "adaptive" => Ok(Difficulty::Adaptive),
+        "mirror" => Ok(Difficulty::Mirror),
+        other => Err(format!("unknown difficulty {}", other)),
+    }
+}
+
+pub fn parse_ruleset(s: &str) -> Result<Ruleset, String> {
+    let ruleset = match s {
+        "classic" => return Ok(Ruleset::Classic),
+        "extended" => Ruleset::Extended,
+        s if s.starts_with("rps:") => {
+            let n = s["rps:".len()..]
+                .parse()
+                .map_err(|_| format!("invalid ruleset {}", s))?;
+            Ruleset::Custom(CustomRuleset::generalized(n, None)?)
+        }
+        path => Ruleset::Custom(CustomRuleset::load(path)?),
+    };
+    check_ruleset(&ruleset).map_err(|e| e.describe(&ruleset))?;
+    Ok(ruleset)
+}
+
+fn parse_format(s: &str) -> Result<MatchFormat, String> {
+    let invalid = || format!("invalid format {}", s);
+    if s == "single" {
+        return Ok(MatchFormat::SingleRound);
+    }
+    let (kind, n) = s.split_once(':').ok_or_else(invalid)?;
+    let n: u32 = n.parse().map_err(|_| invalid())?;
+    match kind {
+        "bestof" if n >= 1 => Ok(MatchFormat::BestOfN(n)),
+        "firstto" if n >= 1 => Ok(MatchFormat::FirstToK(n)),
+        "deuce" if n >= 1 => Ok(MatchFormat::FirstToKWinBy2(n)),
+        "points" if n >= 1 => Ok(MatchFormat::Points { target: n }),
+        _ => Err(invalid()),
+    }
+}
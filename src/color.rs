@@ -0,0 +1,128 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+static THEME: AtomicUsize = AtomicUsize::new(0);
+
+// Takes precedence over the theme picked in the settings.
+const THEME_ENV: &str = "RPS_THEME";
+
+// SGR codes for each kind of text; an empty code leaves it plain.
+struct Theme {
+    name: &'static str,
+    title: &'static str,
+    accent: &'static str,
+    win: &'static str,
+    lose: &'static str,
+    tie: &'static str,
+    notice: &'static str,
+}
+
+const THEMES: [Theme; 3] = [
+    Theme {
+        name: "default",
+        title: "32",
+        accent: "36",
+        win: "32",
+        lose: "31",
+        tie: "33",
+        notice: "33",
+    },
+    // Bold and nothing else, which is all NO_COLOR leaves us.
+    Theme {
+        name: "mono",
+        title: "1",
+        accent: "1",
+        win: "1",
+        lose: "",
+        tie: "",
+        notice: "1",
+    },
+    Theme {
+        name: "neon",
+        title: "1;95",
+        accent: "96",
+        win: "92",
+        lose: "91",
+        tie: "93",
+        notice: "95",
+    },
+];
+
+const MONO: usize = 1;
+
+// https://no-color.org: any value of NO_COLOR rules out colors, though not
+// bold text.
+fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some()
+}
+
+pub fn set_enabled(on: bool) {
+    ENABLED.store(on, Ordering::Relaxed);
+}
+
+fn set_theme(name: &str) -> bool {
+    match THEMES
+        .iter()
+        .position(|t| t.name.eq_ignore_ascii_case(name.trim()))
+    {
+        Some(i) => {
+            THEME.store(i, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+// Returns the name that was asked for if there's no such theme, in which
+// case the theme stays as it was.
+pub fn apply_theme(configured: Option<&str>) -> Result<(), String> {
+    let from_env = std::env::var(THEME_ENV).ok();
+    match from_env.as_deref().or(configured) {
+        Some(name) if !set_theme(name) => Err(name.to_string()),
+        _ => Ok(()),
+    }
+}
+
+pub fn theme_names() -> Vec<&'static str> {
+    THEMES.iter().map(|t| t.name).collect()
+}
+
+fn theme() -> &'static Theme {
+    if no_color() {
+        &THEMES[MONO]
+    } else {
+        &THEMES[THEME.load(Ordering::Relaxed)]
+    }
+}
+
+fn paint(s: &str, code: &str) -> String {
+    if ENABLED.load(Ordering::Relaxed) && !code.is_empty() {
+        format!("\x1b[{}m{}\x1b[0m", code, s)
+    } else {
+        s.to_string()
+    }
+}
+
+pub fn title(s: &str) -> String {
+    paint(s, theme().title)
+}
+
+pub fn accent(s: &str) -> String {
+    paint(s, theme().accent)
+}
+
+pub fn win(s: &str) -> String {
+    paint(s, theme().win)
+}
+
+pub fn lose(s: &str) -> String {
+    paint(s, theme().lose)
+}
+
+pub fn tie(s: &str) -> String {
+    paint(s, theme().tie)
+}
+
+pub fn notice(s: &str) -> String {
+    paint(s, theme().notice)
+}
@@ -0,0 +1,196 @@
+use crate::color::{lose, win};
+use crate::lang::{t, tf};
+use crate::{
+    clear_screen, menu_item, pause, print_heading, read_line, read_menu_choice, read_yes_no,
+};
+use rps_game::{
+    undecided_pair, validate_ruleset, BeatsTable, CustomRuleset, Move, Ruleset, RULESET_DIR,
+};
+use std::fs;
+use std::path::Path;
+
+pub fn ruleset_editor() {
+    clear_screen();
+
+    print_heading(t("editor.title"));
+
+    let name = loop {
+        let s = read_line(t("editor.name"));
+        if !s.is_empty() {
+            break s;
+        }
+        println!("{}", t("setup.name_empty"));
+    };
+
+    println!("\n{}", t("editor.moves_hint"));
+    let mut moves: Vec<String> = Vec::new();
+    loop {
+        let s = read_line(&tf("editor.move_prompt", &[&(moves.len() + 1)]));
+        if s.is_empty() {
+            if moves.len() >= 2 {
+                break;
+            }
+            println!("{}", t("editor.too_few"));
+        } else if moves.iter().any(|m| m.eq_ignore_ascii_case(&s)) {
+            println!("{}", tf("editor.duplicate", &[&s]));
+        } else if moves.len() == u8::MAX as usize {
+            println!("{}", tf("editor.too_many", &[&u8::MAX]));
+            break;
+        } else {
+            moves.push(s);
+        }
+    }
+
+    let all: Vec<Move> = (0..moves.len()).map(|i| Move::Custom(i as u8)).collect();
+    let mut table: BeatsTable = all.iter().map(|&m| (m, Vec::new())).collect();
+
+    loop {
+        clear_screen();
+        println!("{}\n", tf("editor.ruleset", &[&name]));
+        print_table(&moves, &table);
+        let problem = print_status(&name, &moves, &table);
+
+        println!();
+        for (i, m) in moves.iter().enumerate() {
+            menu_item(i + 1, tf("editor.edit", &[m]));
+        }
+        let save = moves.len() as i32 + 1;
+        menu_item(save, t("editor.save"));
+        menu_item(save + 1, t("editor.discard"));
+
+        match read_menu_choice(1, save + 1) {
+            n if n == save => {
+                if problem {
+                    println!("\n{}", t("editor.fix_first"));
+                    pause();
+                    continue;
+                }
+                let rules = CustomRuleset::from_table(&name, moves.clone(), &table);
+                if save_ruleset(&rules) {
+                    return;
+                }
+            }
+            n if n == save + 1 => return,
+            n => edit_move(&name, &moves, &mut table, n as usize - 1),
+        }
+    }
+}
+
+fn edit_move(name: &str, moves: &[String], table: &mut BeatsTable, i: usize) {
+    let mv = Move::Custom(i as u8);
+    loop {
+        clear_screen();
+        println!("{}\n", tf("editor.what_beats", &[&moves[i]]));
+        for (j, other) in moves.iter().enumerate() {
+            if j == i {
+                continue;
+            }
+            let mark = if table[&mv].contains(&Move::Custom(j as u8)) {
+                "x"
+            } else {
+                " "
+            };
+            menu_item(j + 1, format!("[{}] {}", mark, other));
+        }
+        println!();
+        print_status(name, moves, table);
+
+        let s = read_line(&format!("\n{}", t("editor.toggle")));
+        if s.is_empty() {
+            return;
+        }
+        match s.parse::<usize>() {
+            Ok(j) if j >= 1 && j <= moves.len() && j - 1 != i => {
+                let other = Move::Custom(j as u8 - 1);
+                let beaten = table.get_mut(&mv).unwrap();
+                if let Some(pos) = beaten.iter().position(|&m| m == other) {
+                    beaten.remove(pos);
+                } else {
+                    beaten.push(other);
+                    // Two moves can't beat each other, so claiming one
+                    // direction drops the other.
+                    table.get_mut(&other).unwrap().retain(|&m| m != mv);
+                }
+            }
+            _ => println!("{}", t("common.invalid_choice")),
+        }
+    }
+}
+
+fn print_table(moves: &[String], table: &BeatsTable) {
+    for (i, m) in moves.iter().enumerate() {
+        let mut beaten: Vec<usize> = table[&Move::Custom(i as u8)]
+            .iter()
+            .filter_map(|mv| match *mv {
+                Move::Custom(j) => Some(j as usize),
+                _ => None,
+            })
+            .collect();
+        beaten.sort();
+        let names: Vec<&str> = beaten.iter().map(|&j| moves[j].as_str()).collect();
+        if names.is_empty() {
+            println!("  {}", tf("editor.beats_nothing", &[m]));
+        } else {
+            println!("  {}", tf("editor.beats", &[m, &names.join(", ")]));
+        }
+    }
+}
+
+// Returns true when the ruleset can't be saved yet.
+fn print_status(name: &str, moves: &[String], table: &BeatsTable) -> bool {
+    let ruleset = Ruleset::Custom(CustomRuleset::from_table(name, moves.to_vec(), table));
+    let all = Move::all_for_ruleset(&ruleset);
+
+    let problem = if let Some((a, b)) = undecided_pair(&all, table) {
+        Some(tf(
+            "editor.undecided",
+            &[&ruleset.move_name(a), &ruleset.move_name(b)],
+        ))
+    } else {
+        validate_ruleset(&all, table)
+            .err()
+            .map(|e| e.describe(&ruleset))
+    };
+
+    match &problem {
+        Some(p) => println!("\n{}", tf("editor.status", &[&lose(p)])),
+        None => println!("\n{}", tf("editor.status", &[&win(t("editor.ok"))])),
+    }
+    problem.is_some()
+}
+
+fn save_ruleset(rules: &CustomRuleset) -> bool {
+    let stem: String = rules
+        .name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let path = format!("{}/{}.json", RULESET_DIR, stem);
+
+    if Path::new(&path).exists() && !read_yes_no(&format!("\n{}", tf("editor.overwrite", &[&path])))
+    {
+        return false;
+    }
+
+    let result = fs::create_dir_all(RULESET_DIR)
+        .map_err(|e| tf("editor.mkdir_failed", &[&RULESET_DIR, &e]))
+        .and_then(|_| rules.save(&path));
+    let saved = match result {
+        Ok(()) => {
+            println!("\n{}", tf("editor.saved", &[&path]));
+            true
+        }
+        Err(e) => {
+            println!("\n{}", e);
+            false
+        }
+    };
+    pause();
+    saved
+}
@@ -0,0 +1,181 @@
+use rps_game::read_input_line;
+use std::io::{self, IsTerminal, Read};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+// A timed read that gave up leaves its reader thread blocked on stdin. The
+// line it eventually gets belongs to whoever reads next, so it's parked here
+// instead of being lost or racing a second reader.
+static PENDING: Mutex<Option<Receiver<Option<String>>>> = Mutex::new(None);
+
+// Moves from --script, handed to move prompts ahead of the keyboard.
+static SCRIPT: Mutex<Option<Script>> = Mutex::new(None);
+
+struct Script {
+    path: String,
+    lines: Vec<(usize, String)>,
+    next: usize,
+}
+
+pub struct ScriptLine {
+    pub path: String,
+    pub number: usize,
+    pub text: String,
+}
+
+static SINGLE_KEY: AtomicBool = AtomicBool::new(false);
+
+// Lines from somewhere other than stdin, such as a test's script. None at
+// the end, the same as a closed stdin.
+pub trait LineSource: Send {
+    fn next_line(&mut self) -> Option<String>;
+}
+
+static SOURCE: Mutex<Option<Box<dyn LineSource>>> = Mutex::new(None);
+
+pub enum Timed {
+    Line(Option<String>),
+    TimedOut,
+}
+
+#[cfg(test)]
+pub fn set_source(source: Box<dyn LineSource>) {
+    *SOURCE.lock().unwrap_or_else(|e| e.into_inner()) = Some(source);
+}
+
+fn read_stdin_line() -> Option<String> {
+    let mut source = SOURCE.lock().unwrap_or_else(|e| e.into_inner());
+    match source.as_mut() {
+        Some(source) => source.next_line(),
+        None => read_input_line(&mut io::stdin().lock()),
+    }
+}
+
+// Blank lines and lines starting with # are skipped.
+pub fn load_script(path: &str) -> io::Result<()> {
+    let text = std::fs::read_to_string(path)?;
+    let lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim().to_string()))
+        .filter(|(_, l)| !l.is_empty() && !l.starts_with('#'))
+        .collect();
+    *SCRIPT.lock().unwrap_or_else(|e| e.into_inner()) = Some(Script {
+        path: path.to_string(),
+        lines,
+        next: 0,
+    });
+    Ok(())
+}
+
+pub fn scripted() -> bool {
+    SCRIPT.lock().is_ok_and(|s| s.is_some())
+}
+
+// Whether the next line will come from the script rather than a person.
+pub fn script_pending() -> bool {
+    let guard = SCRIPT.lock().unwrap_or_else(|e| e.into_inner());
+    guard.as_ref().is_some_and(|s| s.next < s.lines.len())
+}
+
+// None once the script is used up, or if there never was one.
+pub fn next_script_line() -> Option<ScriptLine> {
+    let mut guard = SCRIPT.lock().unwrap_or_else(|e| e.into_inner());
+    let script = guard.as_mut()?;
+    let (number, text) = script.lines.get(script.next)?.clone();
+    script.next += 1;
+    Some(ScriptLine {
+        path: script.path.clone(),
+        number,
+        text,
+    })
+}
+
+pub fn set_single_key(on: bool) {
+    SINGLE_KEY.store(on, Ordering::SeqCst);
+}
+
+// Off when stdin isn't a terminal, so piped input keeps working line by line.
+pub fn single_key() -> bool {
+    SINGLE_KEY.load(Ordering::SeqCst) && io::stdin().is_terminal()
+}
+
+// One keypress, without waiting for Enter and without echo. None if
+// single_key() is off, so the caller reads a line instead.
+#[cfg(unix)]
+pub fn key() -> Option<char> {
+    // A reader left behind by a timed prompt owns stdin until it gets a line.
+    let pending = PENDING.lock().unwrap_or_else(|e| e.into_inner()).is_some();
+    if !single_key() || pending {
+        return None;
+    }
+
+    // SAFETY: fd 0 is open for the life of the process and termios is plain
+    // old data.
+    let old = unsafe {
+        let mut term: libc::termios = std::mem::zeroed();
+        if libc::tcgetattr(0, &mut term) != 0 {
+            return None;
+        }
+        let mut raw = term;
+        raw.c_lflag &= !(libc::ICANON | libc::ECHO);
+        raw.c_cc[libc::VMIN] = 1;
+        raw.c_cc[libc::VTIME] = 0;
+        if libc::tcsetattr(0, libc::TCSANOW, &raw) != 0 {
+            return None;
+        }
+        term
+    };
+    crate::interrupt::set_hidden_input(true);
+    let mut buf = [0u8; 1];
+    let read = io::stdin().lock().read(&mut buf);
+    crate::interrupt::set_hidden_input(false);
+    // SAFETY: as above; old is the state tcgetattr gave us.
+    unsafe {
+        libc::tcsetattr(0, libc::TCSANOW, &old);
+    }
+
+    match read {
+        Ok(1) => Some(buf[0] as char),
+        _ => None,
+    }
+}
+
+#[cfg(not(unix))]
+pub fn key() -> Option<char> {
+    None
+}
+
+fn take_pending() -> Option<Receiver<Option<String>>> {
+    PENDING.lock().unwrap_or_else(|e| e.into_inner()).take()
+}
+
+// Returns None once stdin is closed.
+pub fn line() -> Option<String> {
+    match take_pending() {
+        Some(rx) => rx.recv().ok().flatten(),
+        None => read_stdin_line(),
+    }
+}
+
+pub fn line_within(timeout: Duration) -> Timed {
+    let rx = take_pending().unwrap_or_else(|| {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(read_stdin_line());
+        });
+        rx
+    });
+
+    match rx.recv_timeout(timeout) {
+        Ok(line) => Timed::Line(line),
+        Err(RecvTimeoutError::Disconnected) => Timed::Line(None),
+        Err(RecvTimeoutError::Timeout) => {
+            *PENDING.lock().unwrap_or_else(|e| e.into_inner()) = Some(rx);
+            Timed::TimedOut
+        }
+    }
+}
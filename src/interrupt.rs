@@ -0,0 +1,100 @@
+use crate::lang::{t, tf};
+use rps_game::{save_conflict, save_game, MatchFormat, MatchState, Scoreboard};
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+// The handler runs on its own thread, so the match in progress is mirrored
+// here whenever it changes rather than borrowed from run_match.
+static ACTIVE: Mutex<Option<(MatchState, Scoreboard)>> = Mutex::new(None);
+static HIDDEN_INPUT: AtomicBool = AtomicBool::new(false);
+
+pub fn install() {
+    if let Err(e) = ctrlc::set_handler(on_interrupt) {
+        println!("{}", tf("interrupt.no_handler", &[&e]));
+    }
+}
+
+pub fn track(state: &MatchState, scoreboard: &Scoreboard) {
+    if let Ok(mut active) = ACTIVE.lock() {
+        *active = Some((state.clone(), scoreboard.clone()));
+    }
+}
+
+pub fn clear() {
+    if let Ok(mut active) = ACTIVE.lock() {
+        *active = None;
+    }
+}
+
+// read_password and single-key input turn echo off until they're done; an
+// interrupt in between would otherwise leave the terminal silent after we
+// exit.
+pub fn set_hidden_input(on: bool) {
+    HIDDEN_INPUT.store(on, Ordering::SeqCst);
+}
+
+fn on_interrupt() {
+    if HIDDEN_INPUT.load(Ordering::SeqCst) {
+        restore_terminal();
+    }
+    let key = if save_active() {
+        "interrupt.saved"
+    } else {
+        "interrupt.exiting"
+    };
+    println!("\n\n{}", t(key));
+    process::exit(130);
+}
+
+// Nothing more can be read once stdin is closed, so the match in progress is
+// kept the same way as on Ctrl-C rather than prompting forever.
+pub fn input_closed() -> ! {
+    let key = if save_active() {
+        "interrupt.saved"
+    } else {
+        "interrupt.exiting"
+    };
+    println!("\n\n{} {}", t("interrupt.input_closed"), t(key));
+    process::exit(0);
+}
+
+// Returns whether the match was saved.
+fn save_active() -> bool {
+    let active = ACTIVE.lock().ok().and_then(|mut a| a.take());
+    let Some((mut state, scoreboard)) = active else {
+        return false;
+    };
+    // Same rule as the in-match "save" command.
+    let savable = !matches!(state.config.format, MatchFormat::SingleRound) && !state.in_event();
+    if savable {
+        // No one to ask, so the other match keeps its slot.
+        if save_conflict(&state).is_some() {
+            state.save_slot = None;
+        }
+        save_game(&mut state, &scoreboard);
+    }
+    scoreboard.save();
+    savable
+}
+
+#[cfg(unix)]
+fn restore_terminal() {
+    use std::os::fd::AsRawFd;
+
+    let Ok(tty) = std::fs::File::open("/dev/tty") else {
+        return;
+    };
+    let fd = tty.as_raw_fd();
+    // SAFETY: fd stays open for the duration and termios is plain old data.
+    unsafe {
+        let mut term: libc::termios = std::mem::zeroed();
+        if libc::tcgetattr(fd, &mut term) == 0 {
+            term.c_lflag |= libc::ECHO | libc::ICANON;
+            libc::tcsetattr(fd, libc::TCSANOW, &term);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn restore_terminal() {}
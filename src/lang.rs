@@ -0,0 +1,1402 @@
+use rps_game::{Achievement, Difficulty, Move, MoveAliases, Ruleset};
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+pub const LANG_ENV: &str = "RPS_LANG";
+
+#[derive(Clone, Copy)]
+enum Lang {
+    English,
+    Spanish,
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+// Accepts plain codes as well as locale strings like "es_ES.UTF-8".
+fn from_env() -> Lang {
+    let code = std::env::var(LANG_ENV).unwrap_or_default().to_lowercase();
+    if code.starts_with("es") {
+        Lang::Spanish
+    } else {
+        Lang::English
+    }
+}
+
+fn table() -> &'static [(&'static str, &'static str)] {
+    match LANG.get_or_init(from_env) {
+        Lang::English => EN,
+        Lang::Spanish => ES,
+    }
+}
+
+fn lookup(table: &'static [(&str, &'static str)], key: &str) -> Option<&'static str> {
+    table.iter().find(|(k, _)| *k == key).map(|&(_, v)| v)
+}
+
+// Missing keys fall back to English, then to the key itself so a typo shows
+// up on screen instead of as a blank line.
+pub fn t(key: &'static str) -> &'static str {
+    lookup(table(), key)
+        .or_else(|| lookup(EN, key))
+        .unwrap_or(key)
+}
+
+// Fills each "{}" in the string with the next argument.
+pub fn tf(key: &'static str, args: &[&dyn Display]) -> String {
+    let mut parts = t(key).split("{}");
+    let mut out = parts.next().unwrap_or_default().to_string();
+    for (i, part) in parts.enumerate() {
+        if let Some(arg) = args.get(i) {
+            out.push_str(&arg.to_string());
+        }
+        out.push_str(part);
+    }
+    out
+}
+
+// Display names only; parsing and saved data keep the English names.
+pub fn move_name(ruleset: &Ruleset, mv: Move) -> &str {
+    match mv {
+        Move::Rock => t("move.rock"),
+        Move::Paper => t("move.paper"),
+        Move::Scissors => t("move.scissors"),
+        Move::Lizard => t("move.lizard"),
+        Move::Spock => t("move.spock"),
+        Move::Custom(_) => ruleset.move_name(mv),
+    }
+}
+
+// Ruleset::name is also the scoreboard key, so it stays untranslated there.
+pub fn ruleset_name(ruleset: &Ruleset) -> &str {
+    match ruleset {
+        Ruleset::Classic => t("ruleset.classic"),
+        Ruleset::Extended => t("ruleset.extended"),
+        Ruleset::Custom(_) => ruleset.name(),
+    }
+}
+
+pub fn difficulty_name(difficulty: Difficulty) -> &'static str {
+    match difficulty {
+        Difficulty::Easy => t("difficulty.easy"),
+        Difficulty::Normal => t("difficulty.normal"),
+        Difficulty::Hard => t("difficulty.hard"),
+        Difficulty::Expert => t("difficulty.expert"),
+        Difficulty::Adaptive => t("difficulty.adaptive"),
+        Difficulty::Mirror => t("difficulty.mirror"),
+    }
+}
+
+pub fn achievement_name(achievement: Achievement) -> &'static str {
+    match achievement {
+        Achievement::FirstWin => t("achievement.first_win"),
+        Achievement::TenWins => t("achievement.ten_wins"),
+        Achievement::Flawless => t("achievement.flawless"),
+        Achievement::Comeback => t("achievement.comeback"),
+        Achievement::BeatHardAi => t("achievement.beat_hard_ai"),
+    }
+}
+
+// Every language's move names, so players can type the word they know.
+pub fn move_aliases() -> MoveAliases {
+    let keys = [
+        ("move.rock", "rock"),
+        ("move.paper", "paper"),
+        ("move.scissors", "scissors"),
+        ("move.lizard", "lizard"),
+        ("move.spock", "spock"),
+    ];
+    let mut aliases = MoveAliases::new();
+    for table in [EN, ES] {
+        for (key, name) in keys {
+            if let Some(word) = lookup(table, key) {
+                aliases.insert(word.to_lowercase(), name.to_string());
+            }
+        }
+    }
+    aliases
+}
+
+const EN: &[(&str, &str)] = &[
+    ("common.back", "Back"),
+    ("common.choose", "Choose: "),
+    ("common.invalid", "Invalid."),
+    ("common.invalid_choice", "Invalid choice. Try again."),
+    ("common.yes_no", "Please answer y or n."),
+    ("common.enter_back", "Press Enter to go back."),
+    ("common.enter_continue", "Press Enter to continue."),
+    ("common.read_failed", "Couldn't read {}: {}"),
+    ("common.write_failed", "Warning: couldn't write {}: {}"),
+    ("banner.title", "Rock, Paper, Scissors"),
+    (
+        "banner.welcome",
+        "Welcome to the Rock, Paper, Scissors game.",
+    ),
+    ("banner.enter", "Press Enter to continue to the main menu."),
+    ("main.title", "Main Menu"),
+    ("main.choose", "Choose an option (1-{}):"),
+    ("main.new", "Start a new game"),
+    ("main.quick", "Quick play (one round vs Normal)"),
+    ("main.challenge", "Daily challenge"),
+    ("main.continue", "Continue saved game"),
+    ("main.tournament", "Start a tournament"),
+    ("main.continue_tournament", "Continue tournament"),
+    ("main.series", "Play a series of matches"),
+    ("main.replay", "Replay a match"),
+    ("main.editor", "Ruleset editor"),
+    ("main.scoreboard", "View scoreboard"),
+    ("main.reset", "Reset match history + scoreboard"),
+    ("main.settings", "Settings"),
+    ("main.exit", "Exit"),
+    ("main.reset_done", "All data reset (save + scoreboard)."),
+    ("main.goodbye", "Goodbye."),
+    ("streak.win", "W{}"),
+    ("streak.loss", "L{}"),
+    ("saves.none", "No saved game found."),
+    (
+        "warn.scoreboard_moved",
+        "{} couldn't be read, so the scoreboard starts empty. The old file was moved to {}.",
+    ),
+    (
+        "warn.scoreboard_unreadable",
+        "{} couldn't be read, so the scoreboard starts empty.",
+    ),
+    (
+        "warn.event_log",
+        "Couldn't write to the event log {} ({}). The match goes on without it.",
+    ),
+    ("saves.title", "Saved games"),
+    (
+        "saves.entry",
+        "Slot {}: {} v. {}  |  {} round(s) played  |  {} - {}",
+    ),
+    (
+        "saves.migrated",
+        "(saved by an older version, upgraded from save v{})",
+    ),
+    (
+        "saves.overwrite",
+        "Slot {} now holds a different match ({} v. {}). Overwrite it? (y/n): ",
+    ),
+    ("saves.new_slot", "Saved to slot {} instead."),
+    ("sb.empty", "No matches played yet."),
+    (
+        "sb.empty_hint",
+        "Play some matches so that the score can be displayed.",
+    ),
+    ("sb.title", "Scoreboard"),
+    ("sb.sort_mw", "Sort by matches won"),
+    ("sb.sort_wr", "Sort by win rate"),
+    ("sb.sort_rw", "Sort by rounds won"),
+    ("sb.sort_elo", "Sort by ELO rating"),
+    ("sb.provisional", "Provisional (fewer than {} matches):"),
+    ("settings.title", "Settings"),
+    ("settings.colors", "Colors"),
+    ("settings.graphics", "Graphics"),
+    ("settings.animation", "Countdown delay (ms)"),
+    ("settings.ai_memory", "AI memory (moves)"),
+    ("settings.single_key", "Single-key moves"),
+    (
+        "settings.single_key_prompt",
+        "Pick moves with a single keypress, without Enter? (y/n): ",
+    ),
+    ("settings.sound", "Bell when a match ends"),
+    ("settings.art_dir", "Move art folder"),
+    (
+        "settings.art_dir_prompt",
+        "Folder with rock.txt, paper.txt and so on (blank for {}): ",
+    ),
+    (
+        "warn.art_empty",
+        "{} is empty, so the built-in art is used instead.",
+    ),
+    (
+        "warn.art_unreadable",
+        "{} couldn't be read ({}), so the built-in art is used instead.",
+    ),
+    (
+        "warn.art_not_ascii",
+        "{} has characters that aren't plain ASCII, so the built-in art is used instead.",
+    ),
+    ("settings.theme", "Color theme"),
+    (
+        "warn.theme_unknown",
+        "There's no color theme called {}; the choices are {}.",
+    ),
+    (
+        "settings.sound_prompt",
+        "Ring the terminal bell when a match ends? One beep for a win, two for a loss, three for a tie. (y/n): ",
+    ),
+    (
+        "match.key_hint",
+        "Press a move's key, or Enter to type a move or command.",
+    ),
+    ("settings.ask", "ask each time"),
+    (
+        "settings.animation_prompt",
+        "Countdown delay in milliseconds (blank for {}): ",
+    ),
+    ("settings.ranking", "Matches before a win rate is ranked"),
+    (
+        "settings.ranking_prompt",
+        "Matches before a win rate is ranked (blank for {}): ",
+    ),
+    ("settings.saved", "Settings saved."),
+    ("sb.h2h", "Head-to-head record"),
+    ("sb.details", "Player details"),
+    ("sb.remove", "Remove player"),
+    ("sb.reset_all", "Reset all stats"),
+    ("sb.remove_prompt", "Player to remove: "),
+    ("sb.removed", "Removed {} from the scoreboard."),
+    (
+        "sb.no_player",
+        "There's no player called \"{}\" on the scoreboard.",
+    ),
+    (
+        "sb.reset_confirm",
+        "Reset every player's stats? This can't be undone. (y/n): ",
+    ),
+    ("sb.reset_done", "All stats reset."),
+    ("sb.import", "Import scoreboard"),
+    (
+        "sb.import_offer",
+        "Import a scoreboard from another file? (y/n): ",
+    ),
+    (
+        "sb.import_prompt",
+        "Scoreboard file to import (blank to cancel): ",
+    ),
+    (
+        "sb.import_same",
+        "That's the scoreboard already in use, so there's nothing to import.",
+    ),
+    ("sb.import_failed", "Couldn't import {}: {}"),
+    ("sb.export", "Export standings"),
+    ("sb.export_prompt", "File to write (blank for {}): "),
+    ("sb.exported", "Wrote {} ({} players)."),
+    ("sb.export_failed", "Couldn't write {}: {}"),
+    ("sb.export_profile", "Export a player's profile"),
+    (
+        "sb.profile_exported",
+        "Wrote {}'s profile to {}. Import it on another machine to carry the stats over.",
+    ),
+    (
+        "sb.imported",
+        "Merged {} players ({} new here), with {} matches played between them.",
+    ),
+    ("sb.player_prompt", "Player: "),
+    ("sb.overall", "Overall"),
+    ("sb.moves", "Moves: {}"),
+    ("sb.play_time", "Time played: {}"),
+    ("sb.response", "Avg response: {} (fastest {})"),
+    ("sb.series", "Series won: {} of {}"),
+    ("col.player", "Player"),
+    ("col.mp", "MP"),
+    ("col.mw", "MW"),
+    ("col.rw", "RW"),
+    ("col.win_rate", "Win Rate"),
+    ("col.round_win_rate", "Round Win%"),
+    ("col.streak", "Streak"),
+    ("col.best", "Best"),
+    ("col.elo", "ELO"),
+    ("col.w", "W"),
+    ("col.t", "T"),
+    ("col.l", "L"),
+    ("h2h.title", "Head-to-head"),
+    ("h2h.first", "First player: "),
+    ("h2h.second", "Second player: "),
+    (
+        "h2h.invalid",
+        "Enter two different players from the scoreboard.",
+    ),
+    ("h2h.none", "{} and {} haven't played each other yet."),
+    ("h2h.ties", "Ties: {}"),
+    ("setup.title", "New Game Setup"),
+    ("setup.mode", "Mode:"),
+    ("setup.single", "Single-player"),
+    ("setup.multi", "Multiplayer"),
+    ("setup.player_name", "Player name: "),
+    ("setup.p1_name", "Player 1 name: "),
+    ("setup.p2_name", "Player 2 name: "),
+    ("setup.name_empty", "Name can't be empty."),
+    ("setup.default", "Press Enter for {}."),
+    (
+        "setup.p2_invalid",
+        "Name can't be empty and must be different from Player 1.",
+    ),
+    ("setup.ruleset", "Ruleset:"),
+    ("setup.format", "Format:"),
+    ("setup.difficulty", "Difficulty:"),
+    ("setup.timer", "Seconds per move (blank for no limit): "),
+    ("setup.advanced", "Configure advanced options? (y/n): "),
+    (
+        "setup.persona",
+        "Play against {} instead of the Computer? (y/n): ",
+    ),
+    (
+        "setup.ai_hints",
+        "Explain the computer's moves after each round? (y/n): ",
+    ),
+    (
+        "setup.weight_recent",
+        "Have Hard weight your latest moves more heavily? (y/n): ",
+    ),
+    (
+        "hint.frequency",
+        "The computer predicted you'd play {} (you've played it {} of your last {} moves).",
+    ),
+    (
+        "hint.cycle",
+        "The computer predicted you'd play {} (your moves have been repeating every {}).",
+    ),
+    ("hint.countered", "It played {} to beat it."),
+    (
+        "hint.switched",
+        "It played {} instead of repeating the move that just lost.",
+    ),
+    (
+        "setup.reveal_difficulty",
+        "Show the computer's difficulty during the match? (y/n): ",
+    ),
+    (
+        "setup.ai_memory",
+        "AI memory in moves (1-{}, blank for {}): ",
+    ),
+    (
+        "setup.counter_chance",
+        "Chance in % that Normal counters your move (0-100, blank for {}): ",
+    ),
+    ("setup.replay_ties", "Replay tied rounds instead of counting them? (y/n): "),
+    (
+        "setup.sudden_death",
+        "Ties in a row before sudden death (blank for no limit): ",
+    ),
+    (
+        "setup.max_rounds",
+        "Most rounds to play before the match is called (blank for no limit): ",
+    ),
+    (
+        "setup.handicap",
+        "Handicap in round wins for {} and {}, e.g. 2-0 (blank for none): ",
+    ),
+    (
+        "setup.commit",
+        "Show a hash of each move before the reveal? (y/n): ",
+    ),
+    (
+        "setup.shared_screen",
+        "Type both moves on one screen and reveal them together? (y/n): ",
+    ),
+    (
+        "setup.ranked",
+        "Count this match on the scoreboard? Answer n for practice. (y/n): ",
+    ),
+    ("setup.colors", "Use colors? (y/n): "),
+    ("setup.graphics", "Graphics:"),
+    ("graphics.none", "None"),
+    ("graphics.ascii", "ASCII art"),
+    ("graphics.emoji", "Emoji"),
+    ("change.ruleset", "Change ruleset:"),
+    ("change.format", "Change match format:"),
+    ("change.difficulty", "Change difficulty:"),
+    ("format.single", "Single round"),
+    ("format.best_of_n", "Best of N"),
+    ("format.first_to_k", "First to K wins"),
+    ("format.win_by_2", "First to K wins, win by 2"),
+    ("format.points", "Points (win = 3, tie = 1)"),
+    (
+        "format.enter_n",
+        "Enter N (>= 1; an even N can end in a draw): ",
+    ),
+    ("format.enter_k", "Enter K (>= 1): "),
+    ("format.enter_points", "Enter target points (>= 1): "),
+    ("ruleset.classic", "Classic"),
+    ("ruleset.extended", "Extended"),
+    ("ruleset.custom", "Custom (load from file)"),
+    ("ruleset.saved", "Saved ruleset (from {}/)"),
+    ("ruleset.file", "Ruleset file: "),
+    (
+        "ruleset.generalized",
+        "Generalized (any odd number of moves)",
+    ),
+    (
+        "ruleset.generalized_size",
+        "Number of moves (odd, at least 3): ",
+    ),
+    (
+        "ruleset.generalized_names",
+        "Move names, comma-separated (blank for M1, M2, ...): ",
+    ),
+    (
+        "ruleset.none_saved",
+        "No saved rulesets yet; make one in the ruleset editor.",
+    ),
+    ("difficulty.easy", "Easy"),
+    ("difficulty.normal", "Normal"),
+    ("difficulty.hard", "Hard"),
+    ("difficulty.expert", "Expert"),
+    ("difficulty.adaptive", "Adaptive"),
+    (
+        "difficulty.adaptive_hint",
+        "expects your least-played moves",
+    ),
+    ("difficulty.mirror", "Mirror"),
+    ("difficulty.mirror_hint", "counters your previous move"),
+    ("move.rock", "Rock"),
+    ("move.paper", "Paper"),
+    ("move.scissors", "Scissors"),
+    ("move.lizard", "Lizard"),
+    ("move.spock", "Spock"),
+    ("header.title", "Match Details"),
+    ("header.practice", "Practice (unranked)"),
+    ("header.players", "Players:"),
+    ("header.ruleset", "Ruleset:"),
+    ("header.format", "Format:"),
+    ("header.difficulty", "Difficulty:"),
+    ("header.timer", "Move timer:"),
+    ("header.handicap", "Handicap:"),
+    ("header.score", "SCORE"),
+    ("header.best_of", "Best of {}"),
+    ("header.first_to", "First to {} wins"),
+    ("header.win_by_2", "First to {} wins, win by 2"),
+    ("header.first_to_points", "First to {} points"),
+    ("header.wins_needed", "Wins needed:"),
+    ("header.target_wins", "Target wins:"),
+    ("header.target_points", "Target points:"),
+    ("header.wins_to_go", "Wins to go:"),
+    ("header.points_to_go", "Points to go:"),
+    ("header.needs", "{} needs {} | {} needs {}"),
+    ("header.match_point", "Match point for {}!"),
+    ("header.double_match_point", "Double match point!"),
+    ("header.outlook", "Outlook:"),
+    (
+        "outlook.random",
+        "{} {}% | {} {}% to win if moves were random",
+    ),
+    ("outlook.observed", "Rounds won so far: {} {}% | {} {}%"),
+    ("header.round", "Round:"),
+    ("header.trend", "Trend:"),
+    ("round.trend", "Trend for {}: {}"),
+    ("match.quick", "Quick actions:"),
+    ("match.continue_round", "Continue to round {}"),
+    ("match.continue_only", "Continue to the 1st and only round"),
+    ("match.leave_tournament", "Leave the tournament"),
+    ("match.leave_series", "Leave the series"),
+    ("match.leave_challenge", "Leave the challenge"),
+    ("match.menu", "Return to main menu"),
+    ("match.save_now", "Save now (return to main menu)"),
+    ("match.menu_no_save", "Return to main menu without saving"),
+    ("discard.summary", "Score: {} {} - {} {}, rounds played: {}"),
+    (
+        "discard.confirm",
+        "Leave without saving? This match will be lost. (y/n): ",
+    ),
+    ("match.accepted", "Accepted inputs: {}"),
+    ("match.or", "OR"),
+    (
+        "match.save_hint",
+        "Type 'save' to save now and return to menu.",
+    ),
+    (
+        "match.command_hint",
+        "Type 'history' for the rounds so far, 'rules' for what beats what, or ':q' to leave.",
+    ),
+    ("rules.title", "Rules: {}"),
+    ("quit.title", "Leave the match?"),
+    ("quit.keep_playing", "Keep playing"),
+    ("match.times_up", "Time's up! {} was picked for you."),
+    ("match.turn", "{}'s turn"),
+    ("match.locked", "{} locked in."),
+    ("match.pass_to", "Press Enter to pass to {}."),
+    ("match.both_locked", "Both moves are locked in."),
+    ("match.both_ready", "Both players ready — revealing."),
+    ("match.hidden_prompt", "{} move (hidden): "),
+    ("match.reveal", "Press Enter to reveal the result."),
+    ("match.move_prompt", "{} move: "),
+    ("match.invalid_move", "Invalid move."),
+    ("match.ambiguous", "Did you mean {}? Type it out in full."),
+    (
+        "match.ambiguous_hidden",
+        "That could be more than one move. Type it out in full.",
+    ),
+    ("match.chose", "{} chose: {}"),
+    (
+        "sudden.notice",
+        "Sudden death: ties are replayed until someone wins.",
+    ),
+    ("sudden.tie", "Tie in sudden death. Play the round again."),
+    ("match.tie_replayed", "Tie. Ties don't count, so play the round again."),
+    ("countdown.rock", "Rock..."),
+    ("countdown.paper", "Paper..."),
+    ("countdown.scissors", "Scissors..."),
+    ("countdown.shoot", "Shoot!"),
+    ("round.options", "Options:"),
+    ("round.next", "Next round"),
+    ("round.history", "View match history"),
+    ("round.concede", "Concede the match"),
+    ("concede.who", "Who concedes?"),
+    (
+        "concede.confirm",
+        "{} concedes and the match goes to {} as a loss. Concede? (y/n): ",
+    ),
+    ("round.winner", "Round winner: {}"),
+    ("round.vs", "VS"),
+    ("round.winner_tie", "Round winner: Nobody (it's a tie)"),
+    ("round.in_a_row", "{} has won {} rounds in a row!"),
+    ("round.score", "Current Score: {} {} - {} {}"),
+    ("round.wins_to_go", "Wins to go: {} {}, {} {}"),
+    ("round.points_to_go", "Points to go: {} {}, {} {}"),
+    ("history.title", "Match history"),
+    ("history.empty", "No rounds played yet."),
+    ("history.tie", "Tie"),
+    ("history.row", "Round {}: {} v. {}  ->  {}"),
+    ("history.no_match", "No rounds match."),
+    ("history.count", "Showing {} of {} rounds."),
+    ("history.filter", "Show:"),
+    ("history.all", "All rounds"),
+    ("history.won_by", "Rounds {} won"),
+    ("history.ties", "Ties"),
+    ("history.played_by", "Rounds where {} played a given move"),
+    ("history.pick_move", "Which move?"),
+    ("victory.complete", "Match complete."),
+    ("victory.final_score", "Final Score: {} {} - {} {}"),
+    ("victory.tie", "It ended in a tie."),
+    (
+        "victory.round_limit",
+        "The {}-round limit was reached, so round wins decided it.",
+    ),
+    (
+        "victory.decided",
+        "{} clinched it in round {}, playing {} to beat {}'s {}.",
+    ),
+    (
+        "victory.decided_tie",
+        "{} clinched it in round {} with a tie, {} against {}'s {}.",
+    ),
+    ("tiebreak.offer", "Play a tiebreaker round? (y/n): "),
+    ("tiebreak.title", "Tiebreaker round"),
+    (
+        "tiebreak.again",
+        "Tied again. The tiebreaker is played again.",
+    ),
+    ("history.tiebreaker", "tiebreaker"),
+    ("victory.winner", "Winner: {}"),
+    ("victory.conceded", "{} conceded the match."),
+    ("victory.duration", "Match time: {}"),
+    ("victory.difficulty", "{} was playing on {}."),
+    (
+        "victory.predicted",
+        "{} correctly predicted your move {}/{} times ({}%)",
+    ),
+    ("histogram.title", "Moves played"),
+    ("post.title", "Post match:"),
+    ("post.rematch", "Rematch (same settings)"),
+    (
+        "post.change_rules",
+        "Change ruleset / format (then rematch)",
+    ),
+    ("post.change_difficulty", "Change difficulty (then rematch)"),
+    ("post.export", "Export match to CSV"),
+    (
+        "post.keep_memory",
+        "Let the computer remember your moves from this match? (y/n): ",
+    ),
+    ("sides.title", "Sides for the rematch:"),
+    ("sides.keep", "Keep them"),
+    ("sides.swap", "Swap them"),
+    ("sides.random", "Pick at random"),
+    ("sides.first", "{} is Player 1."),
+    ("post.exported", "Match exported to {}"),
+    ("replay.title", "Replay a match"),
+    ("replay.slot", "Slot {}: {} v. {}  |  {} round(s)"),
+    ("replay.export", "Export: {}"),
+    ("replay.none", "No saved or exported matches found."),
+    ("replay.no_rounds", "That match has no rounds to replay."),
+    ("replay.manual", "Press Enter to advance"),
+    ("replay.auto", "Advance automatically"),
+    ("replay.round", "Replay: round {} of {}"),
+    ("replay.next", "Press Enter for the next round."),
+    ("replay.end", "End of replay. Final score: {} {} - {} {}"),
+    ("replay.bad_fields", "{}: line {} doesn't have 6 fields"),
+    ("replay.bad_number", "{}: line {} has a bad number"),
+    ("tournament.title", "Tournament Setup"),
+    ("tournament.count", "Number of players (>= 3): "),
+    ("tournament.player_name", "Player {} name: "),
+    (
+        "tournament.name_invalid",
+        "Name can't be empty and must be different from the other players.",
+    ),
+    ("tournament.best_of", "Each match is best of (>= 1): "),
+    ("tournament.final", "Final standings"),
+    (
+        "tournament.left_early",
+        "Tournament left early ({} of {} matches played)",
+    ),
+    (
+        "tournament.saved",
+        "It's saved; pick \"Continue tournament\" from the main menu to finish it.",
+    ),
+    ("tournament.none_saved", "No saved tournament found."),
+    (
+        "tournament.replace_saved",
+        "Starting a new tournament replaces the saved one. Continue? (y/n): ",
+    ),
+    ("challenge.title", "Daily Challenge: {}"),
+    (
+        "challenge.about",
+        "First to 5 against Hard. Everyone playing today faces the same computer,\nand only your first attempt counts.",
+    ),
+    ("challenge.name", "Your name: "),
+    ("challenge.played", "You've played today's challenge already."),
+    ("challenge.left", "Challenge left unfinished; nothing was recorded."),
+    ("challenge.leaderboard", "Today's results:"),
+    ("challenge.nobody", "Nobody has finished today's challenge yet."),
+    ("challenge.won", "won {}-{} in {} rounds"),
+    ("challenge.lost", "lost {}-{} in {} rounds"),
+    ("series.title", "Series"),
+    ("series.target", "Match wins needed to take the series (>= 1): "),
+    ("series.standing", "{} {} - {} {} (first to {})"),
+    ("series.ties", "Drawn matches: {}"),
+    ("series.winner", "{} wins the series {}-{}!"),
+    ("series.left_early", "Series left early after {} matches."),
+    ("editor.title", "Ruleset Editor"),
+    ("editor.name", "Ruleset name: "),
+    (
+        "editor.moves_hint",
+        "Enter the moves one per line, blank to finish.",
+    ),
+    ("editor.move_prompt", "Move {}: "),
+    ("editor.too_few", "A ruleset needs at least two moves."),
+    ("editor.duplicate", "{} is already listed."),
+    ("editor.too_many", "A ruleset can have at most {} moves."),
+    ("editor.ruleset", "Ruleset: {}"),
+    ("editor.edit", "Edit what {} beats"),
+    ("editor.save", "Save"),
+    ("editor.discard", "Discard"),
+    ("editor.fix_first", "Fix the ruleset before saving."),
+    ("editor.what_beats", "What does {} beat?"),
+    ("editor.toggle", "Toggle a move (blank when done): "),
+    ("editor.beats_nothing", "{} beats nothing"),
+    ("editor.beats", "{} beats {}"),
+    ("editor.undecided", "Nothing decides {} v. {} yet."),
+    ("editor.status", "Status: {}"),
+    ("editor.ok", "balanced and complete"),
+    ("editor.overwrite", "{} exists. Overwrite? (y/n): "),
+    ("editor.mkdir_failed", "Couldn't create {}: {}"),
+    ("editor.saved", "Saved to {}."),
+    (
+        "interrupt.no_handler",
+        "Warning: couldn't install the Ctrl-C handler: {}",
+    ),
+    ("interrupt.saved", "Game saved, exiting."),
+    ("interrupt.exiting", "Exiting."),
+    ("interrupt.input_closed", "Input ended."),
+    ("victory.achievement", "Achievement unlocked for {}: {}"),
+    ("achievement.first_win", "First win"),
+    ("achievement.ten_wins", "Win 10 matches"),
+    ("achievement.flawless", "Win a match without losing a round"),
+    ("achievement.comeback", "Win after being match-point down"),
+    ("achievement.beat_hard_ai", "Beat the Hard AI"),
+    ("sb.achievements", "Achievements: {}"),
+    ("commit.hash", "Commitment: {}"),
+    ("commit.title", "Revealing the committed moves"),
+    ("commit.revealed", "{} played {} with nonce {}"),
+    ("commit.matches", "matches commitment {}"),
+    ("commit.mismatch", "does NOT match commitment {}"),
+    ("network.listening", "Waiting for the other player on {}..."),
+    ("network.connected", "{} connected."),
+    ("network.bind_failed", "Couldn't listen on {}: {}"),
+    ("network.connect_failed", "Couldn't connect to {}: {}"),
+    (
+        "network.name_taken",
+        "The other player's name ({}) is already in use.",
+    ),
+    (
+        "network.not_in_save",
+        "{} isn't a player in this saved match.",
+    ),
+    (
+        "network.bad_message",
+        "the other side sent something unexpected",
+    ),
+    (
+        "network.bad_reveal",
+        "the other side's move didn't match its commitment",
+    ),
+    ("network.peer_left", "the other player left"),
+    ("network.not_connected", "not connected"),
+    (
+        "network.waiting_move",
+        "Move locked in. Waiting for the other player...",
+    ),
+    ("network.lost", "Connection lost: {}"),
+    (
+        "network.save_prompt",
+        "Save the match to continue later? (y/n): ",
+    ),
+    ("network.host_prompt", "Address to host on (e.g. :4000): "),
+];
+
+const ES: &[(&str, &str)] = &[
+    ("common.back", "Volver"),
+    ("common.choose", "Elige: "),
+    ("common.invalid", "No válido."),
+    (
+        "common.invalid_choice",
+        "Opción no válida. Inténtalo de nuevo.",
+    ),
+    ("common.yes_no", "Responde y o n."),
+    ("common.enter_back", "Pulsa Enter para volver."),
+    ("common.enter_continue", "Pulsa Enter para continuar."),
+    ("common.read_failed", "No se pudo leer {}: {}"),
+    ("common.write_failed", "Aviso: no se pudo escribir {}: {}"),
+    ("banner.title", "Piedra, Papel o Tijera"),
+    (
+        "banner.welcome",
+        "Bienvenido al juego de Piedra, Papel o Tijera.",
+    ),
+    ("banner.enter", "Pulsa Enter para ir al menú principal."),
+    ("main.title", "Menú principal"),
+    ("main.choose", "Elige una opción (1-{}):"),
+    ("main.new", "Empezar una partida nueva"),
+    ("main.quick", "Partida rápida (una ronda contra Normal)"),
+    ("main.challenge", "Reto diario"),
+    ("main.continue", "Continuar una partida guardada"),
+    ("main.tournament", "Empezar un torneo"),
+    ("main.continue_tournament", "Continuar el torneo"),
+    ("main.series", "Jugar una serie de partidas"),
+    ("main.replay", "Repetir una partida"),
+    ("main.editor", "Editor de reglas"),
+    ("main.scoreboard", "Ver el marcador"),
+    ("main.reset", "Borrar historial y marcador"),
+    ("main.settings", "Ajustes"),
+    ("main.exit", "Salir"),
+    (
+        "main.reset_done",
+        "Datos borrados (partida guardada y marcador).",
+    ),
+    ("main.goodbye", "Adiós."),
+    ("streak.win", "V{}"),
+    ("streak.loss", "D{}"),
+    ("saves.none", "No hay ninguna partida guardada."),
+    (
+        "warn.scoreboard_moved",
+        "No se pudo leer {}, así que el marcador empieza vacío. El archivo anterior se movió a {}.",
+    ),
+    (
+        "warn.scoreboard_unreadable",
+        "No se pudo leer {}, así que el marcador empieza vacío.",
+    ),
+    (
+        "warn.event_log",
+        "No se pudo escribir en el registro de eventos {} ({}). La partida sigue sin él.",
+    ),
+    ("saves.title", "Partidas guardadas"),
+    (
+        "saves.entry",
+        "Ranura {}: {} contra {}  |  {} ronda(s) jugada(s)  |  {} - {}",
+    ),
+    (
+        "saves.migrated",
+        "(guardada con una versión anterior, actualizada desde v{})",
+    ),
+    (
+        "saves.overwrite",
+        "La ranura {} ahora tiene otra partida ({} contra {}). ¿Sobrescribirla? (y/n): ",
+    ),
+    ("saves.new_slot", "Guardada en la ranura {}."),
+    ("sb.empty", "Todavía no se ha jugado ninguna partida."),
+    (
+        "sb.empty_hint",
+        "Juega alguna partida para ver el marcador.",
+    ),
+    ("sb.title", "Marcador"),
+    ("sb.sort_mw", "Ordenar por partidas ganadas"),
+    ("sb.sort_wr", "Ordenar por porcentaje de victorias"),
+    ("sb.sort_rw", "Ordenar por rondas ganadas"),
+    ("sb.sort_elo", "Ordenar por ELO"),
+    ("sb.provisional", "Provisionales (menos de {} partidas):"),
+    ("settings.title", "Ajustes"),
+    ("settings.colors", "Colores"),
+    ("settings.graphics", "Gráficos"),
+    ("settings.animation", "Pausa de la cuenta atrás (ms)"),
+    ("settings.ai_memory", "Memoria de la IA (jugadas)"),
+    ("settings.single_key", "Jugadas con una tecla"),
+    (
+        "settings.single_key_prompt",
+        "¿Elegir las jugadas con una sola tecla, sin Enter? (y/n): ",
+    ),
+    ("settings.sound", "Timbre al terminar una partida"),
+    ("settings.art_dir", "Carpeta de dibujos de jugadas"),
+    (
+        "settings.art_dir_prompt",
+        "Carpeta con rock.txt, paper.txt, etc. (en blanco para {}): ",
+    ),
+    (
+        "warn.art_empty",
+        "{} está vacío, así que se usa el dibujo incorporado.",
+    ),
+    (
+        "warn.art_unreadable",
+        "No se pudo leer {} ({}), así que se usa el dibujo incorporado.",
+    ),
+    (
+        "warn.art_not_ascii",
+        "{} tiene caracteres que no son ASCII, así que se usa el dibujo incorporado.",
+    ),
+    ("settings.theme", "Tema de colores"),
+    (
+        "warn.theme_unknown",
+        "No hay ningún tema de colores llamado {}; se puede elegir entre {}.",
+    ),
+    (
+        "settings.sound_prompt",
+        "¿Hacer sonar el timbre de la terminal al terminar una partida? Un pitido si ganas, dos si pierdes, tres si empatas. (y/n): ",
+    ),
+    (
+        "match.key_hint",
+        "Pulsa la tecla de una jugada, o Enter para escribir una jugada o un comando.",
+    ),
+    ("settings.ask", "preguntar cada vez"),
+    (
+        "settings.animation_prompt",
+        "Pausa de la cuenta atrás en milisegundos (en blanco para {}): ",
+    ),
+    (
+        "settings.ranking",
+        "Partidas antes de clasificar el porcentaje de victorias",
+    ),
+    (
+        "settings.ranking_prompt",
+        "Partidas antes de clasificar el porcentaje de victorias (en blanco para {}): ",
+    ),
+    ("settings.saved", "Ajustes guardados."),
+    ("sb.h2h", "Enfrentamientos directos"),
+    ("sb.details", "Detalles de un jugador"),
+    ("sb.remove", "Eliminar jugador"),
+    ("sb.reset_all", "Borrar todas las estadísticas"),
+    ("sb.remove_prompt", "Jugador a eliminar: "),
+    ("sb.removed", "{} eliminado del marcador."),
+    (
+        "sb.no_player",
+        "No hay ningún jugador llamado \"{}\" en el marcador.",
+    ),
+    (
+        "sb.reset_confirm",
+        "¿Borrar las estadísticas de todos? No se puede deshacer. (y/n): ",
+    ),
+    ("sb.reset_done", "Estadísticas borradas."),
+    ("sb.import", "Importar marcador"),
+    (
+        "sb.import_offer",
+        "¿Importar un marcador de otro archivo? (y/n): ",
+    ),
+    (
+        "sb.import_prompt",
+        "Archivo de marcador a importar (en blanco para cancelar): ",
+    ),
+    (
+        "sb.import_same",
+        "Ese es el marcador que ya se está usando, así que no hay nada que importar.",
+    ),
+    ("sb.import_failed", "No se pudo importar {}: {}"),
+    ("sb.export", "Exportar clasificación"),
+    ("sb.export_prompt", "Archivo a escribir (en blanco para {}): "),
+    ("sb.exported", "Se escribió {} ({} jugadores)."),
+    ("sb.export_failed", "No se pudo escribir {}: {}"),
+    ("sb.export_profile", "Exportar el perfil de un jugador"),
+    (
+        "sb.profile_exported",
+        "Se escribió el perfil de {} en {}. Impórtalo en otro equipo para llevarte las estadísticas.",
+    ),
+    (
+        "sb.imported",
+        "Se fusionaron {} jugadores ({} nuevos aquí), con {} partidas jugadas entre ellos.",
+    ),
+    ("sb.player_prompt", "Jugador: "),
+    ("sb.overall", "Total"),
+    ("sb.moves", "Jugadas: {}"),
+    ("sb.play_time", "Tiempo de juego: {}"),
+    ("sb.response", "Respuesta media: {} (la más rápida, {})"),
+    ("sb.series", "Series ganadas: {} de {}"),
+    ("col.player", "Jugador"),
+    ("col.mp", "PJ"),
+    ("col.mw", "PG"),
+    ("col.rw", "RG"),
+    ("col.win_rate", "% Vict."),
+    ("col.round_win_rate", "% Rondas"),
+    ("col.streak", "Racha"),
+    ("col.best", "Mejor"),
+    ("col.elo", "ELO"),
+    ("col.w", "G"),
+    ("col.t", "E"),
+    ("col.l", "P"),
+    ("h2h.title", "Enfrentamientos directos"),
+    ("h2h.first", "Primer jugador: "),
+    ("h2h.second", "Segundo jugador: "),
+    (
+        "h2h.invalid",
+        "Introduce dos jugadores distintos del marcador.",
+    ),
+    ("h2h.none", "{} y {} todavía no han jugado entre sí."),
+    ("h2h.ties", "Empates: {}"),
+    ("setup.title", "Nueva partida"),
+    ("setup.mode", "Modo:"),
+    ("setup.single", "Un jugador"),
+    ("setup.multi", "Multijugador"),
+    ("setup.player_name", "Nombre del jugador: "),
+    ("setup.p1_name", "Nombre del jugador 1: "),
+    ("setup.p2_name", "Nombre del jugador 2: "),
+    ("setup.name_empty", "El nombre no puede estar vacío."),
+    ("setup.default", "Pulsa Enter para {}."),
+    (
+        "setup.p2_invalid",
+        "El nombre no puede estar vacío y debe ser distinto del jugador 1.",
+    ),
+    ("setup.ruleset", "Reglas:"),
+    ("setup.format", "Formato:"),
+    ("setup.difficulty", "Dificultad:"),
+    (
+        "setup.timer",
+        "Segundos por jugada (en blanco, sin límite): ",
+    ),
+    ("setup.advanced", "¿Configurar opciones avanzadas? (y/n): "),
+    (
+        "setup.persona",
+        "¿Jugar contra {} en lugar del ordenador? (y/n): ",
+    ),
+    (
+        "setup.ai_hints",
+        "¿Explicar las jugadas del ordenador después de cada ronda? (y/n): ",
+    ),
+    (
+        "setup.weight_recent",
+        "¿Que Difícil dé más peso a tus últimas jugadas? (y/n): ",
+    ),
+    (
+        "hint.frequency",
+        "El ordenador predijo que jugarías {} (la has jugado {} de tus últimas {} jugadas).",
+    ),
+    (
+        "hint.cycle",
+        "El ordenador predijo que jugarías {} (tus jugadas se repiten cada {}).",
+    ),
+    ("hint.countered", "Jugó {} para ganarle."),
+    (
+        "hint.switched",
+        "Jugó {} para no repetir la jugada que acababa de perder.",
+    ),
+    (
+        "setup.reveal_difficulty",
+        "¿Mostrar la dificultad del ordenador durante la partida? (y/n): ",
+    ),
+    (
+        "setup.ai_memory",
+        "Memoria de la IA en jugadas (1-{}, en blanco para {}): ",
+    ),
+    (
+        "setup.counter_chance",
+        "Probabilidad en % de que Normal contrarreste tu jugada (0-100, en blanco para {}): ",
+    ),
+    (
+        "setup.replay_ties",
+        "¿Repetir las rondas empatadas en lugar de contarlas? (y/n): ",
+    ),
+    (
+        "setup.sudden_death",
+        "Empates seguidos antes de muerte súbita (en blanco, sin límite): ",
+    ),
+    (
+        "setup.max_rounds",
+        "Máximo de rondas antes de dar la partida por terminada (en blanco, sin límite): ",
+    ),
+    (
+        "setup.handicap",
+        "Ventaja en rondas ganadas para {} y {}, p. ej. 2-0 (en blanco, ninguna): ",
+    ),
+    (
+        "setup.commit",
+        "¿Mostrar un hash de cada jugada antes de revelarla? (y/n): ",
+    ),
+    (
+        "setup.shared_screen",
+        "¿Escribir las dos jugadas en la misma pantalla y revelarlas juntas? (y/n): ",
+    ),
+    (
+        "setup.ranked",
+        "¿Contar esta partida en el marcador? Responde n para practicar. (y/n): ",
+    ),
+    ("setup.colors", "¿Usar colores? (y/n): "),
+    ("setup.graphics", "Gráficos:"),
+    ("graphics.none", "Ninguno"),
+    ("graphics.ascii", "Dibujos ASCII"),
+    ("graphics.emoji", "Emoji"),
+    ("change.ruleset", "Cambiar reglas:"),
+    ("change.format", "Cambiar formato:"),
+    ("change.difficulty", "Cambiar dificultad:"),
+    ("format.single", "Una sola ronda"),
+    ("format.best_of_n", "Al mejor de N"),
+    ("format.first_to_k", "El primero en ganar K"),
+    ("format.win_by_2", "El primero en ganar K, con 2 de ventaja"),
+    ("format.points", "Puntos (victoria = 3, empate = 1)"),
+    (
+        "format.enter_n",
+        "Introduce N (>= 1; con N par puede acabar en empate): ",
+    ),
+    ("format.enter_k", "Introduce K (>= 1): "),
+    (
+        "format.enter_points",
+        "Introduce los puntos objetivo (>= 1): ",
+    ),
+    ("ruleset.classic", "Clásicas"),
+    ("ruleset.extended", "Ampliadas"),
+    ("ruleset.custom", "Personalizadas (desde un archivo)"),
+    ("ruleset.saved", "Reglas guardadas (en {}/)"),
+    ("ruleset.file", "Archivo de reglas: "),
+    (
+        "ruleset.generalized",
+        "Generalizadas (cualquier número impar de jugadas)",
+    ),
+    (
+        "ruleset.generalized_size",
+        "Número de jugadas (impar, al menos 3): ",
+    ),
+    (
+        "ruleset.generalized_names",
+        "Nombres de las jugadas, separados por comas (en blanco para M1, M2, ...): ",
+    ),
+    (
+        "ruleset.none_saved",
+        "Aún no hay reglas guardadas; crea unas en el editor de reglas.",
+    ),
+    ("difficulty.easy", "Fácil"),
+    ("difficulty.normal", "Normal"),
+    ("difficulty.hard", "Difícil"),
+    ("difficulty.expert", "Experto"),
+    ("difficulty.adaptive", "Adaptativa"),
+    (
+        "difficulty.adaptive_hint",
+        "espera tus jugadas menos usadas",
+    ),
+    ("difficulty.mirror", "Espejo"),
+    ("difficulty.mirror_hint", "contrarresta tu jugada anterior"),
+    ("move.rock", "Piedra"),
+    ("move.paper", "Papel"),
+    ("move.scissors", "Tijera"),
+    ("move.lizard", "Lagarto"),
+    ("move.spock", "Spock"),
+    ("header.title", "Datos de la partida"),
+    ("header.practice", "Práctica (sin clasificar)"),
+    ("header.players", "Jugadores:"),
+    ("header.ruleset", "Reglas:"),
+    ("header.format", "Formato:"),
+    ("header.difficulty", "Dificultad:"),
+    ("header.timer", "Tiempo:"),
+    ("header.handicap", "Ventaja:"),
+    ("header.score", "MARCADOR"),
+    ("header.best_of", "Al mejor de {}"),
+    ("header.first_to", "El primero en ganar {}"),
+    ("header.win_by_2", "El primero en ganar {}, con 2 de ventaja"),
+    ("header.first_to_points", "El primero en llegar a {} puntos"),
+    ("header.wins_needed", "Victorias:"),
+    ("header.target_wins", "Objetivo:"),
+    ("header.target_points", "Puntos meta:"),
+    ("header.wins_to_go", "Faltan:"),
+    ("header.points_to_go", "Faltan:"),
+    ("header.needs", "a {} le faltan {} | a {} le faltan {}"),
+    ("header.match_point", "¡Bola de partido para {}!"),
+    ("header.double_match_point", "¡Doble bola de partido!"),
+    ("header.outlook", "Panorama:"),
+    (
+        "outlook.random",
+        "{} {}% | {} {}% de ganar si las jugadas fueran al azar",
+    ),
+    (
+        "outlook.observed",
+        "Rondas ganadas hasta ahora: {} {}% | {} {}%",
+    ),
+    ("header.round", "Ronda:"),
+    ("header.trend", "Tendencia:"),
+    ("round.trend", "Tendencia de {}: {}"),
+    ("match.quick", "Acciones rápidas:"),
+    ("match.continue_round", "Continuar a la ronda {}"),
+    ("match.continue_only", "Continuar a la única ronda"),
+    ("match.leave_tournament", "Abandonar el torneo"),
+    ("match.leave_series", "Abandonar la serie"),
+    ("match.leave_challenge", "Abandonar el reto"),
+    ("match.menu", "Volver al menú principal"),
+    ("match.save_now", "Guardar ahora (volver al menú principal)"),
+    ("match.menu_no_save", "Volver al menú principal sin guardar"),
+    ("discard.summary", "Marcador: {} {} - {} {}, rondas jugadas: {}"),
+    (
+        "discard.confirm",
+        "¿Salir sin guardar? Esta partida se perderá. (y/n): ",
+    ),
+    ("match.accepted", "Entradas válidas: {}"),
+    ("match.or", "O"),
+    (
+        "match.save_hint",
+        "Escribe 'save' para guardar y volver al menú.",
+    ),
+    (
+        "match.command_hint",
+        "Escribe 'history' para ver las rondas, 'rules' para ver qué gana a qué o ':q' para salir.",
+    ),
+    ("rules.title", "Reglas: {}"),
+    ("quit.title", "¿Salir de la partida?"),
+    ("quit.keep_playing", "Seguir jugando"),
+    (
+        "match.times_up",
+        "¡Se acabó el tiempo! Se eligió {} por ti.",
+    ),
+    ("match.turn", "Turno de {}"),
+    ("match.locked", "{} ya ha elegido."),
+    ("match.pass_to", "Pulsa Enter para pasarle el turno a {}."),
+    ("match.both_locked", "Los dos han elegido."),
+    ("match.both_ready", "Los dos están listos; se revelan las jugadas."),
+    ("match.hidden_prompt", "Jugada de {} (oculta): "),
+    ("match.reveal", "Pulsa Enter para ver el resultado."),
+    ("match.move_prompt", "Jugada de {}: "),
+    ("match.invalid_move", "Jugada no válida."),
+    ("match.ambiguous", "¿Querías decir {}? Escríbela entera."),
+    (
+        "match.ambiguous_hidden",
+        "Eso puede ser más de una jugada. Escríbela entera.",
+    ),
+    ("match.chose", "{} eligió: {}"),
+    (
+        "sudden.notice",
+        "Muerte súbita: los empates se repiten hasta que alguien gane.",
+    ),
+    ("sudden.tie", "Empate en muerte súbita. Se repite la ronda."),
+    ("match.tie_replayed", "Empate. Los empates no cuentan, así que se repite la ronda."),
+    ("countdown.rock", "Piedra..."),
+    ("countdown.paper", "Papel..."),
+    ("countdown.scissors", "Tijera..."),
+    ("countdown.shoot", "¡Ya!"),
+    ("round.options", "Opciones:"),
+    ("round.next", "Siguiente ronda"),
+    ("round.history", "Ver el historial de la partida"),
+    ("round.concede", "Rendirse"),
+    ("concede.who", "¿Quién se rinde?"),
+    (
+        "concede.confirm",
+        "{} se rinde y la partida es para {}, contando como derrota. ¿Rendirse? (y/n): ",
+    ),
+    ("round.winner", "Ganador de la ronda: {}"),
+    ("round.vs", "VS"),
+    ("round.winner_tie", "Ganador de la ronda: nadie (empate)"),
+    ("round.in_a_row", "¡{} lleva {} rondas seguidas ganadas!"),
+    ("round.score", "Marcador: {} {} - {} {}"),
+    ("round.wins_to_go", "Victorias que faltan: {} {}, {} {}"),
+    ("round.points_to_go", "Puntos que faltan: {} {}, {} {}"),
+    ("history.title", "Historial de la partida"),
+    ("history.empty", "Todavía no se ha jugado ninguna ronda."),
+    ("history.tie", "Empate"),
+    ("history.row", "Ronda {}: {} contra {}  ->  {}"),
+    ("history.no_match", "Ninguna ronda coincide."),
+    ("history.count", "Mostrando {} de {} rondas."),
+    ("history.filter", "Mostrar:"),
+    ("history.all", "Todas las rondas"),
+    ("history.won_by", "Rondas que ganó {}"),
+    ("history.ties", "Empates"),
+    (
+        "history.played_by",
+        "Rondas en las que {} jugó una jugada concreta",
+    ),
+    ("history.pick_move", "¿Qué jugada?"),
+    ("victory.complete", "Partida terminada."),
+    ("victory.final_score", "Resultado final: {} {} - {} {}"),
+    ("victory.tie", "Terminó en empate."),
+    (
+        "victory.round_limit",
+        "Se llegó al límite de {} rondas, así que decidieron las rondas ganadas.",
+    ),
+    (
+        "victory.decided",
+        "{} lo decidió en la ronda {}, jugando {} para ganar a {} ({}).",
+    ),
+    (
+        "victory.decided_tie",
+        "{} lo decidió en la ronda {} con un empate, {} contra {} ({}).",
+    ),
+    ("tiebreak.offer", "¿Jugar una ronda de desempate? (y/n): "),
+    ("tiebreak.title", "Ronda de desempate"),
+    ("tiebreak.again", "Empate otra vez. Se repite el desempate."),
+    ("history.tiebreaker", "desempate"),
+    ("victory.winner", "Ganador: {}"),
+    ("victory.conceded", "{} se rindió."),
+    ("victory.duration", "Duración de la partida: {}"),
+    ("victory.difficulty", "{} jugaba en dificultad {}."),
+    (
+        "victory.predicted",
+        "{} adivinó tu jugada {}/{} veces ({}%)",
+    ),
+    ("histogram.title", "Jugadas"),
+    ("post.title", "Fin de la partida:"),
+    ("post.rematch", "Revancha (mismos ajustes)"),
+    (
+        "post.change_rules",
+        "Cambiar reglas / formato (y jugar la revancha)",
+    ),
+    (
+        "post.change_difficulty",
+        "Cambiar dificultad (y jugar la revancha)",
+    ),
+    ("post.export", "Exportar la partida a CSV"),
+    (
+        "post.keep_memory",
+        "¿Dejar que el ordenador recuerde tus jugadas de esta partida? (y/n): ",
+    ),
+    ("sides.title", "Lados para la revancha:"),
+    ("sides.keep", "Mantenerlos"),
+    ("sides.swap", "Intercambiarlos"),
+    ("sides.random", "Elegir al azar"),
+    ("sides.first", "{} es el Jugador 1."),
+    ("post.exported", "Partida exportada a {}"),
+    ("replay.title", "Repetir una partida"),
+    ("replay.slot", "Ranura {}: {} contra {}  |  {} ronda(s)"),
+    ("replay.export", "Exportada: {}"),
+    ("replay.none", "No hay partidas guardadas ni exportadas."),
+    (
+        "replay.no_rounds",
+        "Esa partida no tiene rondas que repetir.",
+    ),
+    ("replay.manual", "Pulsar Enter para avanzar"),
+    ("replay.auto", "Avanzar automáticamente"),
+    ("replay.round", "Repetición: ronda {} de {}"),
+    ("replay.next", "Pulsa Enter para la siguiente ronda."),
+    (
+        "replay.end",
+        "Fin de la repetición. Resultado final: {} {} - {} {}",
+    ),
+    ("replay.bad_fields", "{}: la línea {} no tiene 6 campos"),
+    (
+        "replay.bad_number",
+        "{}: la línea {} tiene un número incorrecto",
+    ),
+    ("tournament.title", "Preparar un torneo"),
+    ("tournament.count", "Número de jugadores (>= 3): "),
+    ("tournament.player_name", "Nombre del jugador {}: "),
+    (
+        "tournament.name_invalid",
+        "El nombre no puede estar vacío y debe ser distinto de los demás.",
+    ),
+    ("tournament.best_of", "Cada partida es al mejor de (>= 1): "),
+    ("tournament.final", "Clasificación final"),
+    (
+        "tournament.left_early",
+        "Torneo abandonado ({} de {} partidas jugadas)",
+    ),
+    (
+        "tournament.saved",
+        "Está guardado; elige \"Continuar el torneo\" en el menú principal para terminarlo.",
+    ),
+    ("tournament.none_saved", "No hay ningún torneo guardado."),
+    (
+        "tournament.replace_saved",
+        "Empezar un torneo nuevo sustituye al guardado. ¿Continuar? (y/n): ",
+    ),
+    ("challenge.title", "Reto diario: {}"),
+    (
+        "challenge.about",
+        "El primero en llegar a 5 contra Difícil. Hoy todos se enfrentan al mismo ordenador\ny solo cuenta tu primer intento.",
+    ),
+    ("challenge.name", "Tu nombre: "),
+    ("challenge.played", "Ya has jugado el reto de hoy."),
+    ("challenge.left", "Reto sin terminar; no se ha registrado nada."),
+    ("challenge.leaderboard", "Resultados de hoy:"),
+    ("challenge.nobody", "Nadie ha terminado todavía el reto de hoy."),
+    ("challenge.won", "ganó {}-{} en {} rondas"),
+    ("challenge.lost", "perdió {}-{} en {} rondas"),
+    ("series.title", "Serie"),
+    ("series.target", "Victorias necesarias para ganar la serie (>= 1): "),
+    ("series.standing", "{} {} - {} {} (gana quien llegue a {})"),
+    ("series.ties", "Partidas empatadas: {}"),
+    ("series.winner", "¡{} gana la serie {}-{}!"),
+    ("series.left_early", "Serie abandonada tras {} partidas."),
+    ("editor.title", "Editor de reglas"),
+    ("editor.name", "Nombre de las reglas: "),
+    (
+        "editor.moves_hint",
+        "Escribe las jugadas, una por línea; en blanco para terminar.",
+    ),
+    ("editor.move_prompt", "Jugada {}: "),
+    ("editor.too_few", "Hacen falta al menos dos jugadas."),
+    ("editor.duplicate", "{} ya está en la lista."),
+    ("editor.too_many", "Como mucho puede haber {} jugadas."),
+    ("editor.ruleset", "Reglas: {}"),
+    ("editor.edit", "Editar a qué gana {}"),
+    ("editor.save", "Guardar"),
+    ("editor.discard", "Descartar"),
+    ("editor.fix_first", "Corrige las reglas antes de guardar."),
+    ("editor.what_beats", "¿A qué gana {}?"),
+    (
+        "editor.toggle",
+        "Marca o desmarca una jugada (en blanco para terminar): ",
+    ),
+    ("editor.beats_nothing", "{} no gana a nada"),
+    ("editor.beats", "{} gana a {}"),
+    ("editor.undecided", "Todavía nada decide {} contra {}."),
+    ("editor.status", "Estado: {}"),
+    ("editor.ok", "equilibradas y completas"),
+    ("editor.overwrite", "{} ya existe. ¿Sobrescribir? (y/n): "),
+    ("editor.mkdir_failed", "No se pudo crear {}: {}"),
+    ("editor.saved", "Guardadas en {}."),
+    (
+        "interrupt.no_handler",
+        "Aviso: no se pudo instalar el manejador de Ctrl-C: {}",
+    ),
+    ("interrupt.saved", "Partida guardada, saliendo."),
+    ("interrupt.exiting", "Saliendo."),
+    ("interrupt.input_closed", "Se acabó la entrada."),
+    ("victory.achievement", "Logro desbloqueado para {}: {}"),
+    ("achievement.first_win", "Primera victoria"),
+    ("achievement.ten_wins", "Ganar 10 partidas"),
+    (
+        "achievement.flawless",
+        "Ganar una partida sin perder ninguna ronda",
+    ),
+    (
+        "achievement.comeback",
+        "Ganar tras estar a una ronda de perder",
+    ),
+    ("achievement.beat_hard_ai", "Vencer a la IA difícil"),
+    ("sb.achievements", "Logros: {}"),
+    ("commit.hash", "Compromiso: {}"),
+    ("commit.title", "Revelando las jugadas comprometidas"),
+    ("commit.revealed", "{} jugó {} con nonce {}"),
+    ("commit.matches", "coincide con el compromiso {}"),
+    ("commit.mismatch", "NO coincide con el compromiso {}"),
+    ("network.listening", "Esperando al otro jugador en {}..."),
+    ("network.connected", "{} se ha conectado."),
+    ("network.bind_failed", "No se pudo escuchar en {}: {}"),
+    ("network.connect_failed", "No se pudo conectar a {}: {}"),
+    (
+        "network.name_taken",
+        "El nombre del otro jugador ({}) ya está en uso.",
+    ),
+    (
+        "network.not_in_save",
+        "{} no es jugador de esta partida guardada.",
+    ),
+    ("network.bad_message", "el otro lado envió algo inesperado"),
+    (
+        "network.bad_reveal",
+        "la jugada del otro lado no coincide con su compromiso",
+    ),
+    ("network.peer_left", "el otro jugador se ha ido"),
+    ("network.not_connected", "sin conexión"),
+    (
+        "network.waiting_move",
+        "Jugada fijada. Esperando al otro jugador...",
+    ),
+    ("network.lost", "Conexión perdida: {}"),
+    (
+        "network.save_prompt",
+        "¿Guardar la partida para seguir luego? (y/n): ",
+    ),
+    (
+        "network.host_prompt",
+        "Dirección en la que alojar (p. ej. :4000): ",
+    ),
+];
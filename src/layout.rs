@@ -0,0 +1,68 @@
+use terminal_size::{terminal_size, Width};
+
+const MIN_NAME_WIDTH: usize = 8;
+const MAX_NAME_WIDTH: usize = 40;
+
+// None when stdout isn't a terminal and $COLUMNS isn't set either; callers
+// then keep their fixed layout.
+pub fn terminal_width() -> Option<usize> {
+    terminal_size()
+        .map(|(Width(w), _)| w as usize)
+        .or_else(|| std::env::var("COLUMNS").ok()?.trim().parse().ok())
+        .filter(|&w| w > 0)
+}
+
+// The name column gets whatever the `rest` of the row leaves over. One
+// column is kept free so a full-width row doesn't wrap on its own.
+pub fn name_width(rest: usize, default: usize) -> usize {
+    match terminal_width() {
+        Some(w) => w
+            .saturating_sub(rest + 1)
+            .clamp(MIN_NAME_WIDTH, MAX_NAME_WIDTH),
+        None => default,
+    }
+}
+
+// A separator of `len` characters, cut down to the terminal width.
+pub fn rule(ch: char, len: usize) -> String {
+    let len = terminal_width().map_or(len, |w| len.min(w));
+    ch.to_string().repeat(len)
+}
+
+pub fn fit(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+// Two blocks of lines as columns, with `middle` between them on the row
+// halfway down; the shorter block is padded with blank lines. None when the
+// result is wider than the terminal.
+pub fn side_by_side(left: &str, right: &str, middle: &str) -> Option<String> {
+    let left: Vec<&str> = left.lines().collect();
+    let right: Vec<&str> = right.lines().collect();
+    let width = |lines: &[&str]| lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let (left_width, right_width) = (width(&left), width(&right));
+    let middle_width = middle.chars().count() + 4;
+    if terminal_width().is_some_and(|w| left_width + middle_width + right_width >= w) {
+        return None;
+    }
+
+    let rows = left.len().max(right.len());
+    let lines: Vec<String> = (0..rows)
+        .map(|i| {
+            let mid = if i == rows / 2 { middle } else { "" };
+            let line = format!(
+                "{:<left_width$}{:^middle_width$}{}",
+                left.get(i).unwrap_or(&""),
+                mid,
+                right.get(i).unwrap_or(&"")
+            );
+            line.trim_end().to_string()
+        })
+        .collect();
+    Some(lines.join("\n"))
+}
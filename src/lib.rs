@@ -0,0 +1,3457 @@
+//! Rock, Paper, Scissors: the rules, the computer opponent, and the match,
+//! save and scoreboard state behind the `rps` binary.
+//!
+//! The rules on their own are [`Move`], [`Ruleset`], [`beats`] and
+//! [`decide_winner`]:
+//!
+//! ```
+//! use rps_game::{decide_winner, Move, RoundWinner, Ruleset};
+//!
+//! let winner = decide_winner(&Ruleset::Extended, Move::Spock, Move::Scissors);
+//! assert_eq!(winner, RoundWinner::Player1);
+//! ```
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::Duration;
+
+pub const SAVE_FILE: &str = "rps_save.json";
+pub const SAVE_SLOT_PREFIX: &str = "rps_save_";
+pub const SCORE_FILE: &str = "rps_scoreboard.json";
+pub const RULESET_DIR: &str = "rulesets";
+pub const ALIAS_FILE: &str = "rps_aliases.json";
+pub const CONFIG_FILE: &str = "rps_config.json";
+pub const TOURNAMENT_FILE: &str = "rps_tournament.json";
+pub const ART_DIR: &str = "art";
+pub const SEED_ENV: &str = "RPS_SEED";
+pub const TIMER_ENV: &str = "RPS_TIMER_MS";
+pub const DEFAULT_ELO: f64 = 1200.0;
+pub const ELO_K: f64 = 32.0;
+pub const DEFAULT_AI_MEMORY: usize = 12;
+// Percent of Normal's rounds in which it counters the human's move.
+pub const DEFAULT_COUNTER_CHANCE: u8 = 35;
+pub const DEFAULT_MIN_MATCHES_FOR_RANKING: u32 = 5;
+pub const MAX_AI_MEMORY: usize = 100;
+// With recency weighting on, how much each remembered move counts next to
+// the one after it.
+pub const RECENCY_DECAY: f64 = 0.8;
+// Bump when the save layout changes, adding a step to migrate_save.
+pub const SAVE_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Mode {
+    SinglePlayer,
+    Multiplayer,
+    Network,
+}
+
+/// Which moves exist and which beats which.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Ruleset {
+    /// Rock, Paper, Scissors.
+    Classic,
+    /// Rock, Paper, Scissors, Lizard, Spock.
+    Extended,
+    /// Moves and relations loaded from a JSON file; see [`CustomRuleset`].
+    Custom(CustomRuleset),
+}
+
+impl Ruleset {
+    pub fn name(&self) -> &str {
+        match self {
+            Ruleset::Classic => "Classic",
+            Ruleset::Extended => "Extended",
+            Ruleset::Custom(c) => &c.name,
+        }
+    }
+
+    pub fn move_name(&self, mv: Move) -> &str {
+        match (self, mv) {
+            (Ruleset::Custom(c), Move::Custom(i)) => {
+                c.moves.get(i as usize).map(String::as_str).unwrap_or("?")
+            }
+            _ => mv.name(),
+        }
+    }
+}
+
+// Loaded from a JSON file such as:
+// { "name": "Elements", "moves": ["Fire", "Grass", "Water"],
+//   "beats": { "Fire": ["Grass"], "Grass": ["Water"], "Water": ["Fire"] } }
+// Moves are played as Move::Custom(index into `moves`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomRuleset {
+    #[serde(default)]
+    pub name: String,
+    pub moves: Vec<String>,
+    pub beats: HashMap<String, Vec<String>>,
+}
+
+impl CustomRuleset {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let data =
+            fs::read_to_string(path).map_err(|e| format!("Couldn't read {}: {}", path, e))?;
+        let raw: CustomRuleset =
+            serde_json::from_str(&data).map_err(|e| format!("Invalid ruleset file: {}", e))?;
+
+        let mut rules = raw.normalized()?;
+        if rules.name.trim().is_empty() {
+            rules.name = std::path::Path::new(path)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("Custom")
+                .to_string();
+        }
+        Ok(rules)
+    }
+
+    // Checks the table and rewrites every name in `beats` to the spelling
+    // used in `moves`, so later lookups can compare exactly.
+    pub fn normalized(self) -> Result<Self, String> {
+        if self.moves.len() < 2 {
+            return Err("A ruleset needs at least two moves.".to_string());
+        }
+        if self.moves.len() > u8::MAX as usize {
+            return Err(format!("A ruleset can have at most {} moves.", u8::MAX));
+        }
+
+        for (i, m) in self.moves.iter().enumerate() {
+            if m.trim().is_empty() {
+                return Err("Move names can't be empty.".to_string());
+            }
+            if self.moves[..i].iter().any(|o| o.eq_ignore_ascii_case(m)) {
+                return Err(format!("Move {} is listed twice.", m));
+            }
+        }
+
+        let lookup = |name: &str| -> Result<String, String> {
+            self.moves
+                .iter()
+                .find(|m| m.eq_ignore_ascii_case(name.trim()))
+                .cloned()
+                .ok_or_else(|| format!("{} is not one of the listed moves.", name))
+        };
+
+        let mut beats: HashMap<String, Vec<String>> = HashMap::new();
+        for (winner, losers) in &self.beats {
+            let winner = lookup(winner)?;
+            let entry = beats.entry(winner.clone()).or_default();
+            for loser in losers {
+                let loser = lookup(loser)?;
+                if loser == winner {
+                    return Err(format!("{} can't beat itself.", winner));
+                }
+                if !entry.contains(&loser) {
+                    entry.push(loser);
+                }
+            }
+        }
+
+        for m in &self.moves {
+            let mentioned = beats.get(m).is_some_and(|l| !l.is_empty())
+                || beats.values().any(|l| l.contains(m));
+            if !mentioned {
+                return Err(format!("{} doesn't appear in any beats entry.", m));
+            }
+        }
+
+        Ok(CustomRuleset {
+            name: self.name,
+            moves: self.moves,
+            beats,
+        })
+    }
+
+    // Builds the file form from a table over Move::Custom indices into `moves`.
+    pub fn from_table(name: &str, moves: Vec<String>, table: &BeatsTable) -> Self {
+        let name_of = |mv: &Move| match *mv {
+            Move::Custom(i) => moves.get(i as usize).cloned(),
+            _ => None,
+        };
+        let beats = table
+            .iter()
+            .filter_map(|(winner, losers)| {
+                let losers: Vec<String> = losers.iter().filter_map(name_of).collect();
+                Some((name_of(winner)?, losers)).filter(|(_, l)| !l.is_empty())
+            })
+            .collect();
+        CustomRuleset {
+            name: name.to_string(),
+            moves,
+            beats,
+        }
+    }
+
+    // The standard balanced game for any odd n: each move beats the
+    // (n - 1) / 2 moves after it, wrapping around. Moves are M1..Mn unless
+    // `names` has n of them.
+    pub fn generalized(n: usize, names: Option<Vec<String>>) -> Result<Self, String> {
+        if n < 3 || n.is_multiple_of(2) {
+            return Err(
+                "A generalized ruleset needs an odd number of moves, at least 3.".to_string(),
+            );
+        }
+        // The name is what save files and the per-ruleset stats go by, so it
+        // carries n and, when they were picked, the move names.
+        let (name, moves) = match names {
+            Some(names) if names.len() != n => {
+                return Err(format!("Expected {} move names, got {}.", n, names.len()));
+            }
+            Some(names) => (format!("RPS-{} ({})", n, names.join("/")), names),
+            None => (
+                format!("RPS-{}", n),
+                (1..=n).map(|i| format!("M{}", i)).collect(),
+            ),
+        };
+        let beats = moves
+            .iter()
+            .enumerate()
+            .map(|(i, m)| {
+                let losers = (1..=(n - 1) / 2).map(|k| moves[(i + k) % n].clone());
+                (m.clone(), losers.collect())
+            })
+            .collect();
+        CustomRuleset { name, moves, beats }.normalized()
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, json).map_err(|e| format!("Couldn't write {}: {}", path, e))
+    }
+
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.moves.iter().position(|m| m.eq_ignore_ascii_case(name))
+    }
+
+    pub fn beats(&self, a: usize, b: usize) -> bool {
+        match (self.moves.get(a), self.moves.get(b)) {
+            (Some(a), Some(b)) => self.beats.get(a).is_some_and(|l| l.contains(b)),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+    Expert,
+    Adaptive,
+    Mirror,
+}
+
+// A name for the computer at each difficulty. The names aren't translated,
+// so a persona keeps one scoreboard entry whatever the language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Persona {
+    Randall,
+    Nina,
+    Hal,
+    Ada,
+    Otto,
+    Marcus,
+}
+
+impl Persona {
+    pub fn for_difficulty(difficulty: Difficulty) -> Self {
+        match difficulty {
+            Difficulty::Easy => Persona::Randall,
+            Difficulty::Normal => Persona::Nina,
+            Difficulty::Hard => Persona::Hal,
+            Difficulty::Expert => Persona::Ada,
+            Difficulty::Adaptive => Persona::Otto,
+            Difficulty::Mirror => Persona::Marcus,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Persona::Randall => "Randall the Random",
+            Persona::Nina => "Nina the Nudger",
+            Persona::Hal => "Hal the Predictor",
+            Persona::Ada => "Ada the Analyst",
+            Persona::Otto => "Otto the Opportunist",
+            Persona::Marcus => "Marcus the Mirror",
+        }
+    }
+}
+
+// The computer without a persona gets a scoreboard entry per difficulty,
+// untranslated for the same reason. A hidden difficulty plays as plain
+// "Computer" so the name doesn't give it away.
+pub fn computer_name(difficulty: Difficulty, revealed: bool) -> String {
+    let level = match difficulty {
+        Difficulty::Easy => "Easy",
+        Difficulty::Normal => "Normal",
+        Difficulty::Hard => "Hard",
+        Difficulty::Expert => "Expert",
+        Difficulty::Adaptive => "Adaptive",
+        Difficulty::Mirror => "Mirror",
+    };
+    if revealed {
+        format!("Computer ({})", level)
+    } else {
+        "Computer".to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum MatchFormat {
+    SingleRound,
+    BestOfN(u32),
+    FirstToK(u32),
+    // Reaching K only takes the match with a lead of two; until then play
+    // goes on, so pair it with max_rounds to put an end to a long deuce.
+    FirstToKWinBy2(u32),
+    // A round win is worth 3 points and a tie 1 point to each player.
+    Points { target: u32 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameConfig {
+    pub player1: String,
+    pub player2: String,
+    pub mode: Mode,
+    pub ruleset: Ruleset,
+    pub format: MatchFormat,
+    pub difficulty: Option<Difficulty>,
+    #[serde(default)]
+    pub rng_seed: Option<u64>,
+    #[serde(default = "default_true")]
+    pub use_color: bool,
+    #[serde(default = "default_true")]
+    pub show_ascii: bool,
+    // Emoji instead of ASCII art for the moves; see show_ascii for the rest.
+    #[serde(default)]
+    pub show_emoji: bool,
+    #[serde(default)]
+    pub move_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub max_consecutive_ties: Option<u32>,
+    // Hotseat only: a hash of each move is shown before the reveal.
+    #[serde(default)]
+    pub commit_moves: bool,
+    // Round wins each side starts with. They count toward the match but not
+    // toward the scoreboard.
+    #[serde(default)]
+    pub handicap: (u32, u32),
+    // How many of the human's recent moves the AI remembers.
+    #[serde(default = "default_ai_memory")]
+    pub ai_memory: usize,
+    // Blind play: the header leaves the difficulty out until the match ends.
+    #[serde(default = "default_true")]
+    pub reveal_difficulty: bool,
+    // Each round is appended here as a line of JSON while the match is played.
+    #[serde(default)]
+    pub event_log: Option<PathBuf>,
+    // Hard only: each round summary says what the computer predicted and why.
+    #[serde(default)]
+    pub show_ai_hints: bool,
+    // Normal only, 0 to 100; see DEFAULT_COUNTER_CHANCE.
+    #[serde(default = "default_counter_chance")]
+    pub counter_chance: u8,
+    // Single-player: the computer goes by the persona's name, which is then
+    // player2.
+    #[serde(default)]
+    pub persona: Option<Persona>,
+    // Tied rounds aren't recorded and the round is played again, as in
+    // sudden death.
+    #[serde(default)]
+    pub replay_ties: bool,
+    // Rounds after which a match the format hasn't decided goes to whoever
+    // has more round wins.
+    #[serde(default)]
+    pub max_rounds: Option<u32>,
+    // Practice matches leave the scoreboard alone.
+    #[serde(default = "default_true")]
+    pub ranked: bool,
+    // Hard only: recent moves count for more when it guesses by frequency.
+    #[serde(default)]
+    pub weight_recent: bool,
+    // Hotseat only: both moves are typed on one screen, one after the other,
+    // and shown together once both are in.
+    #[serde(default)]
+    pub shared_screen: bool,
+}
+
+// The values a save from before a field existed gets, where there's one.
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            player1: String::new(),
+            player2: String::new(),
+            mode: Mode::SinglePlayer,
+            ruleset: Ruleset::Classic,
+            format: MatchFormat::SingleRound,
+            difficulty: None,
+            rng_seed: None,
+            use_color: true,
+            show_ascii: true,
+            show_emoji: false,
+            move_timeout_secs: None,
+            max_consecutive_ties: None,
+            commit_moves: false,
+            handicap: (0, 0),
+            ai_memory: DEFAULT_AI_MEMORY,
+            reveal_difficulty: true,
+            event_log: None,
+            show_ai_hints: false,
+            counter_chance: DEFAULT_COUNTER_CHANCE,
+            persona: None,
+            replay_ties: false,
+            max_rounds: None,
+            ranked: true,
+            weight_recent: false,
+            shared_screen: false,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_ai_memory() -> usize {
+    DEFAULT_AI_MEMORY
+}
+
+fn default_counter_chance() -> u8 {
+    DEFAULT_COUNTER_CHANCE
+}
+
+/// A move in any ruleset. Custom rulesets refer to their moves by index, so
+/// a `Custom` move only means something together with its [`Ruleset`].
+// Serialized as a plain string ("Rock", "Custom(2)") so moves can also be
+// used as JSON map keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum Move {
+    Rock,
+    Paper,
+    Scissors,
+    Lizard,
+    Spock,
+    Custom(u8),
+}
+
+impl Move {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Move::Rock => "Rock",
+            Move::Paper => "Paper",
+            Move::Scissors => "Scissors",
+            Move::Lizard => "Lizard",
+            Move::Spock => "Spock",
+            Move::Custom(_) => "Custom",
+        }
+    }
+
+    pub fn all_for_ruleset(r: &Ruleset) -> Vec<Move> {
+        match r {
+            Ruleset::Classic => vec![Move::Rock, Move::Paper, Move::Scissors],
+            Ruleset::Extended => vec![
+                Move::Rock,
+                Move::Paper,
+                Move::Scissors,
+                Move::Lizard,
+                Move::Spock,
+            ],
+            Ruleset::Custom(c) => (0..c.moves.len()).map(|i| Move::Custom(i as u8)).collect(),
+        }
+    }
+}
+
+impl From<Move> for String {
+    fn from(mv: Move) -> String {
+        match mv {
+            Move::Custom(i) => format!("Custom({})", i),
+            _ => mv.name().to_string(),
+        }
+    }
+}
+
+impl TryFrom<String> for Move {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Move, String> {
+        let builtin = Move::all_for_ruleset(&Ruleset::Extended)
+            .into_iter()
+            .find(|m| m.name() == s);
+        if let Some(mv) = builtin {
+            return Ok(mv);
+        }
+        s.strip_prefix("Custom(")
+            .and_then(|rest| rest.strip_suffix(')'))
+            .and_then(|n| n.parse().ok())
+            .map(Move::Custom)
+            .ok_or_else(|| format!("unknown move {}", s))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoundRecord {
+    pub round: u32,
+    pub p1_move: Move,
+    pub p2_move: Move,
+    pub winner: RoundWinner,
+    // Played after the match ended drawn, to decide it.
+    #[serde(default)]
+    pub tiebreaker: bool,
+}
+
+/// The outcome of a round, or of a whole match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoundWinner {
+    Player1,
+    Player2,
+    Tie,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchState {
+    pub config: GameConfig,
+    pub round_number: u32,
+    pub p1_round_wins: u32,
+    pub p2_round_wins: u32,
+    pub history: Vec<RoundRecord>,
+    pub human_recent: Vec<Move>,
+    #[serde(default)]
+    pub ai_recent: Vec<Move>,
+    #[serde(default)]
+    pub ai_predictions: Vec<(Move, Move)>,
+    // Unix seconds. Saves from before these were kept have 0 and no time.
+    #[serde(default)]
+    pub started_at: u64,
+    #[serde(default)]
+    pub ended_at: Option<u64>,
+    pub turn: Turn,
+    #[serde(skip, default = "StdRng::from_entropy")]
+    pub rng: StdRng,
+    #[serde(skip)]
+    pub save_slot: Option<u32>,
+    // Tournament pairings can't be saved or rematched; run_match returns as
+    // soon as the match is decided.
+    #[serde(skip)]
+    pub in_tournament: bool,
+    // Same rules as a tournament pairing, apart from the tiebreak.
+    #[serde(skip)]
+    pub in_series: bool,
+    #[serde(skip)]
+    pub in_challenge: bool,
+    // The side a concession handed the match to.
+    #[serde(skip)]
+    pub conceded_to: Option<RoundWinner>,
+    // Set when the save this was loaded from had to be upgraded.
+    #[serde(skip)]
+    pub migrated_from: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Turn {
+    WaitingP1,
+    WaitingP2,
+    Reveal,
+}
+
+impl MatchState {
+    pub fn new(config: GameConfig) -> Self {
+        let turn = match config.mode {
+            Mode::SinglePlayer => Turn::WaitingP1,
+            Mode::Multiplayer => Turn::WaitingP1,
+            Mode::Network => Turn::WaitingP1,
+        };
+        let rng = seeded_rng(config.rng_seed, 0);
+        let (p1_round_wins, p2_round_wins) = config.handicap;
+        Self {
+            config,
+            round_number: 1,
+            p1_round_wins,
+            p2_round_wins,
+            history: vec![],
+            human_recent: vec![],
+            ai_recent: vec![],
+            ai_predictions: vec![],
+            started_at: unix_now(),
+            ended_at: None,
+            turn,
+            rng,
+            save_slot: None,
+            in_tournament: false,
+            in_series: false,
+            in_challenge: false,
+            conceded_to: None,
+            migrated_from: None,
+        }
+    }
+
+    // The RNG itself isn't saved, so a resumed seeded match re-derives it
+    // from the seed and the number of rounds already played.
+    pub fn restore_rng(&mut self) {
+        self.rng = seeded_rng(self.config.rng_seed, self.history.len() as u64);
+    }
+
+    // Scores and stats follow the names, so only the config changes hands.
+    pub fn swap_sides(&mut self) {
+        let cfg = &mut self.config;
+        std::mem::swap(&mut cfg.player1, &mut cfg.player2);
+        cfg.handicap = (cfg.handicap.1, cfg.handicap.0);
+    }
+
+    // Wall-clock time, so a match that sat in a save counts the wait too.
+    pub fn duration_secs(&self) -> Option<u64> {
+        let end = self.ended_at.filter(|_| self.started_at > 0)?;
+        Some(end.saturating_sub(self.started_at))
+    }
+
+    // Tournament pairings, series matches and the daily challenge are played
+    // through without saves or the post-match menu.
+    pub fn in_event(&self) -> bool {
+        self.in_tournament || self.in_series || self.in_challenge
+    }
+
+    pub fn reset_for_rematch(&mut self) {
+        self.round_number = 1;
+        self.started_at = unix_now();
+        self.ended_at = None;
+        (self.p1_round_wins, self.p2_round_wins) = self.config.handicap;
+        self.history.clear();
+        self.human_recent.clear();
+        self.ai_recent.clear();
+        self.ai_predictions.clear();
+        self.turn = Turn::WaitingP1;
+        self.conceded_to = None;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerStats {
+    pub matches_played: u32,
+    pub matches_won: u32,
+    pub rounds_won: u32,
+    // Ties included; handicap rounds and replayed ties aren't.
+    #[serde(default)]
+    pub rounds_played: u32,
+    #[serde(default)]
+    pub rounds_tied: u32,
+    // Positive for consecutive match wins, negative for losses, 0 after a tie.
+    #[serde(default)]
+    pub current_streak: i32,
+    #[serde(default)]
+    pub longest_win_streak: u32,
+    #[serde(default = "default_elo")]
+    pub elo: f64,
+    // Keyed by ruleset name; entries never have a breakdown of their own.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub by_ruleset: HashMap<String, PlayerStats>,
+    // Built-in moves only: a custom move's index means something different
+    // in every custom ruleset.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub move_counts: HashMap<Move, u32>,
+    // Only tracked on the overall stats.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub achievements: HashSet<Achievement>,
+    // Overall stats only, like achievements.
+    #[serde(default)]
+    pub play_time_secs: u64,
+    #[serde(default)]
+    pub series_played: u32,
+    #[serde(default)]
+    pub series_won: u32,
+    // Overall stats only. How long people took over their moves; timed-out
+    // moves and the computer's aren't counted.
+    #[serde(default)]
+    pub responses: u32,
+    #[serde(default)]
+    pub response_ms_total: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fastest_response_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Achievement {
+    FirstWin,
+    TenWins,
+    Flawless,
+    Comeback,
+    BeatHardAi,
+}
+
+fn default_elo() -> f64 {
+    DEFAULT_ELO
+}
+
+impl Default for PlayerStats {
+    fn default() -> Self {
+        Self {
+            matches_played: 0,
+            matches_won: 0,
+            rounds_won: 0,
+            rounds_played: 0,
+            rounds_tied: 0,
+            current_streak: 0,
+            longest_win_streak: 0,
+            elo: DEFAULT_ELO,
+            by_ruleset: HashMap::new(),
+            move_counts: HashMap::new(),
+            achievements: HashSet::new(),
+            play_time_secs: 0,
+            series_played: 0,
+            series_won: 0,
+            responses: 0,
+            response_ms_total: 0,
+            fastest_response_ms: None,
+        }
+    }
+}
+
+impl PlayerStats {
+    // Counts add up. Ratings and streaks can't be combined, so ours are kept
+    // unless we have no matches to go on.
+    fn merge(&mut self, other: &PlayerStats) {
+        if self.matches_played == 0 {
+            self.elo = other.elo;
+            self.current_streak = other.current_streak;
+        }
+        self.matches_played += other.matches_played;
+        self.matches_won += other.matches_won;
+        self.rounds_won += other.rounds_won;
+        self.rounds_played += other.rounds_played;
+        self.rounds_tied += other.rounds_tied;
+        self.longest_win_streak = self.longest_win_streak.max(other.longest_win_streak);
+        for (ruleset, stats) in &other.by_ruleset {
+            self.by_ruleset
+                .entry(ruleset.clone())
+                .or_default()
+                .merge(stats);
+        }
+        for (&mv, &count) in &other.move_counts {
+            *self.move_counts.entry(mv).or_insert(0) += count;
+        }
+        self.achievements.extend(other.achievements.iter().copied());
+        self.play_time_secs += other.play_time_secs;
+        self.series_played += other.series_played;
+        self.series_won += other.series_won;
+        self.responses += other.responses;
+        self.response_ms_total += other.response_ms_total;
+        self.fastest_response_ms = match (self.fastest_response_ms, other.fastest_response_ms) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+// Everything the winner's match qualifies for, unlocked before or not.
+// Stats have to include the match already.
+fn earned_achievements(
+    state: &MatchState,
+    winner: RoundWinner,
+    stats: &PlayerStats,
+) -> Vec<Achievement> {
+    let cfg = &state.config;
+    let mut earned = vec![Achievement::FirstWin];
+    if stats.matches_won >= 10 {
+        earned.push(Achievement::TenWins);
+    }
+    // A single round is too short for either of these to mean anything.
+    if !matches!(cfg.format, MatchFormat::SingleRound) {
+        let loser = match winner {
+            RoundWinner::Player1 => RoundWinner::Player2,
+            _ => RoundWinner::Player1,
+        };
+        if !state.history.iter().any(|r| r.winner == loser) {
+            earned.push(Achievement::Flawless);
+        }
+        if was_match_point_down(state, loser) {
+            earned.push(Achievement::Comeback);
+        }
+    }
+    if cfg.mode == Mode::SinglePlayer
+        && matches!(cfg.difficulty, Some(Difficulty::Hard | Difficulty::Expert))
+    {
+        earned.push(Achievement::BeatHardAi);
+    }
+    earned
+}
+
+// Replays the match and checks whether one more round for `opponent` would
+// have ended it at any point.
+fn was_match_point_down(state: &MatchState, opponent: RoundWinner) -> bool {
+    let mut replay = MatchState::new(state.config.clone());
+    for r in &state.history {
+        let mut what_if = replay.clone();
+        apply_round(&mut what_if, r.p1_move, r.p2_move, opponent);
+        if check_match_winner(&what_if) == Some(opponent) {
+            return true;
+        }
+        apply_round(&mut replay, r.p1_move, r.p2_move, r.winner);
+    }
+    false
+}
+
+// Standard ELO update; `score` is 1.0 for a win, 0.5 for a tie, 0.0 for a loss.
+fn elo_update(rating: f64, opponent: f64, score: f64) -> f64 {
+    let expected = 1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0));
+    rating + ELO_K * (score - expected)
+}
+
+// Applies one finished match to both players' stats. Used for the overall
+// numbers and again for the per-ruleset breakdown.
+pub fn record_match(
+    s1: &mut PlayerStats,
+    s2: &mut PlayerStats,
+    outcome: RoundWinner,
+    p1_rounds: u32,
+    p2_rounds: u32,
+    (played, tied): (u32, u32),
+) {
+    for (s, won) in [(&mut *s1, p1_rounds), (&mut *s2, p2_rounds)] {
+        s.matches_played += 1;
+        s.rounds_won += won;
+        s.rounds_played += played;
+        s.rounds_tied += tied;
+    }
+
+    let p1_score = match outcome {
+        RoundWinner::Player1 => {
+            s1.matches_won += 1;
+            1.0
+        }
+        RoundWinner::Player2 => {
+            s2.matches_won += 1;
+            0.0
+        }
+        RoundWinner::Tie => 0.5,
+    };
+
+    let tied = matches!(outcome, RoundWinner::Tie);
+    s1.record_outcome(matches!(outcome, RoundWinner::Player1), tied);
+    s2.record_outcome(matches!(outcome, RoundWinner::Player2), tied);
+
+    let (elo1, elo2) = (s1.elo, s2.elo);
+    s1.elo = elo_update(elo1, elo2, p1_score);
+    s2.elo = elo_update(elo2, elo1, 1.0 - p1_score);
+}
+
+impl PlayerStats {
+    pub fn average_response(&self) -> Option<Duration> {
+        (self.responses > 0)
+            .then(|| Duration::from_millis(self.response_ms_total / u64::from(self.responses)))
+    }
+
+    pub fn record_outcome(&mut self, won: bool, tied: bool) {
+        self.current_streak = if tied {
+            0
+        } else if won {
+            self.current_streak.max(0) + 1
+        } else {
+            self.current_streak.min(0) - 1
+        };
+        if self.current_streak > 0 {
+            self.longest_win_streak = self.longest_win_streak.max(self.current_streak as u32);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Scoreboard {
+    pub players: HashMap<String, PlayerStats>,
+    // Keyed by the alphabetically smaller name, then the larger one, so a
+    // pair of players always shares one record regardless of seating.
+    #[serde(default)]
+    pub head_to_head: HashMap<String, HashMap<String, HeadToHead>>,
+    // Daily challenge results keyed by date (YYYY-MM-DD), first attempt only.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub challenges: HashMap<String, Vec<ChallengeResult>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChallengeResult {
+    pub player: String,
+    pub winner: RoundWinner,
+    pub rounds_won: u32,
+    pub rounds_lost: u32,
+    pub rounds_played: u32,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct HeadToHead {
+    pub first_wins: u32,
+    pub second_wins: u32,
+    pub ties: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    MatchesWon,
+    WinRate,
+    RoundsWon,
+    Elo,
+}
+
+// A scoreboard row: name, stats and win rate (0 to 1).
+pub type ScoreRow = (String, PlayerStats, f32);
+
+pub fn win_rate(stats: &PlayerStats) -> f32 {
+    if stats.matches_played == 0 {
+        0.0
+    } else {
+        stats.matches_won as f32 / stats.matches_played as f32
+    }
+}
+
+// Best first by `key`, then by matches played and rounds won, then by name,
+// so equal rows always come out in the same order. A NaN rate sorts last.
+pub fn sort_rows(rows: &mut [ScoreRow], key: SortKey) {
+    let rate = |r: f32| if r.is_nan() { f32::NEG_INFINITY } else { r };
+    rows.sort_by(|(a_name, a, a_rate), (b_name, b, b_rate)| {
+        let primary = match key {
+            SortKey::MatchesWon => b.matches_won.cmp(&a.matches_won),
+            SortKey::WinRate => rate(*b_rate).total_cmp(&rate(*a_rate)),
+            SortKey::RoundsWon => b.rounds_won.cmp(&a.rounds_won),
+            SortKey::Elo => b.elo.total_cmp(&a.elo),
+        };
+        primary
+            .then(b.matches_played.cmp(&a.matches_played))
+            .then(b.rounds_won.cmp(&a.rounds_won))
+            .then_with(|| a_name.cmp(b_name))
+    });
+}
+
+// The answers from the last new game, offered again next time. Anything
+// missing is asked for as usual.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Defaults {
+    #[serde(default)]
+    pub ruleset: Option<Ruleset>,
+    #[serde(default)]
+    pub format: Option<MatchFormat>,
+    #[serde(default)]
+    pub difficulty: Option<Difficulty>,
+    #[serde(default)]
+    pub use_color: Option<bool>,
+    #[serde(default)]
+    pub show_ascii: Option<bool>,
+    #[serde(default)]
+    pub show_emoji: Option<bool>,
+    #[serde(default)]
+    pub player1: Option<String>,
+    #[serde(default)]
+    pub player2: Option<String>,
+    // Settings rather than answers; only the settings menu changes these.
+    #[serde(default)]
+    pub min_matches_for_ranking: Option<u32>,
+    #[serde(default)]
+    pub animation_ms: Option<u64>,
+    #[serde(default)]
+    pub ai_memory: Option<usize>,
+    #[serde(default)]
+    pub single_key: Option<bool>,
+    // A terminal bell when a match ends; off unless turned on.
+    #[serde(default)]
+    pub sound: Option<bool>,
+    // Where rock.txt, paper.txt and the rest are looked for; ART_DIR if unset.
+    #[serde(default)]
+    pub art_dir: Option<PathBuf>,
+    // One of the color themes by name; "default" if unset.
+    #[serde(default)]
+    pub theme: Option<String>,
+}
+
+impl Defaults {
+    pub fn load() -> Self {
+        let Ok(data) = fs::read_to_string(CONFIG_FILE) else {
+            return Defaults::default();
+        };
+        serde_json::from_str(&data).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Ok(json) = serde_json::to_string_pretty(self) {
+            let _ = fs::write(CONFIG_FILE, json);
+        }
+    }
+
+    // Players with fewer matches are listed apart when sorting by win rate.
+    pub fn ranking_threshold(&self) -> u32 {
+        self.min_matches_for_ranking
+            .unwrap_or(DEFAULT_MIN_MATCHES_FOR_RANKING)
+    }
+
+    // Single-player keeps the last human opponent's name for next time.
+    pub fn remember(&mut self, cfg: &GameConfig) {
+        self.ruleset = Some(cfg.ruleset.clone());
+        self.format = Some(cfg.format);
+        self.difficulty = cfg.difficulty.or(self.difficulty);
+        self.use_color = Some(cfg.use_color);
+        self.show_ascii = Some(cfg.show_ascii);
+        self.show_emoji = Some(cfg.show_emoji);
+        self.player1 = Some(cfg.player1.clone());
+        if cfg.mode == Mode::Multiplayer {
+            self.player2 = Some(cfg.player2.clone());
+        }
+    }
+}
+
+// Names are compared ignoring case and runs of spaces.
+pub fn canonical_name(name: &str) -> String {
+    tidy_name(name).to_lowercase()
+}
+
+fn tidy_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+impl Scoreboard {
+    // An unreadable file is moved aside rather than overwritten by the next
+    // save; the error is where it went, if it could be moved at all.
+    pub fn load() -> Result<Self, Option<String>> {
+        let Ok(data) = fs::read_to_string(SCORE_FILE) else {
+            return Ok(Scoreboard::default());
+        };
+        serde_json::from_str(&data).map_err(|_| back_up(SCORE_FILE))
+    }
+
+    pub fn save(&self) {
+        if let Ok(json) = serde_json::to_string_pretty(self) {
+            let _ = fs::write(SCORE_FILE, json);
+        }
+    }
+
+    pub fn write_to(&self, path: &str) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+
+    pub fn load_from(path: &str) -> Result<Self, String> {
+        let data = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        serde_json::from_str(&data).map_err(|e| e.to_string())
+    }
+
+    // Everything about one player as a scoreboard of its own, so the file it
+    // is written to can be imported like any other. Opponents show up only in
+    // the head-to-head records.
+    pub fn profile(&self, name: &str) -> Option<Scoreboard> {
+        let key = self.player_key(name);
+        let stats = self.players.get(&key)?;
+        let mut head_to_head: HashMap<String, HashMap<String, HeadToHead>> = HashMap::new();
+        for (first, opponents) in &self.head_to_head {
+            for (second, &record) in opponents {
+                if *first == key || *second == key {
+                    head_to_head
+                        .entry(first.clone())
+                        .or_default()
+                        .insert(second.clone(), record);
+                }
+            }
+        }
+        let challenges = self
+            .challenges
+            .iter()
+            .filter_map(|(date, results)| {
+                let ours: Vec<_> = results
+                    .iter()
+                    .filter(|r| r.player == key)
+                    .cloned()
+                    .collect();
+                (!ours.is_empty()).then(|| (date.clone(), ours))
+            })
+            .collect();
+        Some(Scoreboard {
+            players: HashMap::from([(key, stats.clone())]),
+            head_to_head,
+            challenges,
+        })
+    }
+
+    // Returns how many players were merged, how many of them are new here,
+    // and how many matches they'd played between them.
+    pub fn merge(&mut self, other: Scoreboard) -> (usize, usize, u32) {
+        let mut new = 0;
+        let mut matches = 0;
+        for (name, stats) in &other.players {
+            let key = self.player_key(name);
+            if !self.players.contains_key(&key) {
+                new += 1;
+            }
+            matches += stats.matches_played;
+            self.players.entry(key).or_default().merge(stats);
+        }
+        // Our spelling of a name can sort differently from theirs.
+        for (first, opponents) in other.head_to_head {
+            for (second, record) in opponents {
+                let (a, b) = (self.player_key(&first), self.player_key(&second));
+                let (a, b, record) = if a <= b {
+                    (a, b, record)
+                } else {
+                    let swapped = HeadToHead {
+                        first_wins: record.second_wins,
+                        second_wins: record.first_wins,
+                        ties: record.ties,
+                    };
+                    (b, a, swapped)
+                };
+                let h = self
+                    .head_to_head
+                    .entry(a)
+                    .or_default()
+                    .entry(b)
+                    .or_default();
+                h.first_wins += record.first_wins;
+                h.second_wins += record.second_wins;
+                h.ties += record.ties;
+            }
+        }
+        for (date, results) in other.challenges {
+            for mut result in results {
+                result.player = self.player_key(&result.player);
+                if self.challenge_result(&date, &result.player).is_none() {
+                    self.challenges
+                        .entry(date.clone())
+                        .or_default()
+                        .push(result);
+                }
+            }
+        }
+        (other.players.len(), new, matches)
+    }
+
+    // A name that differs from an entry only in case or spacing finds that
+    // entry, which keeps the spelling it was first seen with.
+    pub fn player_key(&self, name: &str) -> String {
+        let canonical = canonical_name(name);
+        self.players
+            .keys()
+            .find(|k| canonical_name(k) == canonical)
+            .cloned()
+            .unwrap_or_else(|| tidy_name(name))
+    }
+
+    pub fn player(&self, name: &str) -> Option<&PlayerStats> {
+        self.players.get(&self.player_key(name))
+    }
+
+    pub fn ensure_player(&mut self, name: &str) -> String {
+        let key = self.player_key(name);
+        self.players.entry(key.clone()).or_default();
+        key
+    }
+
+    // Returns the achievements the match unlocked, with who earned them.
+    pub fn add_match_result(
+        &mut self,
+        state: &MatchState,
+        outcome: RoundWinner,
+    ) -> Vec<(String, Achievement)> {
+        let cfg = &state.config;
+        let p1 = self.ensure_player(&cfg.player1);
+        let p2 = self.ensure_player(&cfg.player2);
+        let (p1, p2) = (p1.as_str(), p2.as_str());
+        let ruleset = cfg.ruleset.name();
+        // Handicap rounds were never played.
+        let p1_rounds = state.p1_round_wins - cfg.handicap.0;
+        let p2_rounds = state.p2_round_wins - cfg.handicap.1;
+        let tied = state
+            .history
+            .iter()
+            .filter(|r| r.winner == RoundWinner::Tie)
+            .count();
+        let rounds = (state.history.len() as u32, tied as u32);
+        let winner = match outcome {
+            RoundWinner::Player1 => Some(p1),
+            RoundWinner::Player2 => Some(p2),
+            RoundWinner::Tie => None,
+        };
+
+        let mut s1 = self.players[p1].clone();
+        let mut s2 = self.players[p2].clone();
+        record_match(&mut s1, &mut s2, outcome, p1_rounds, p2_rounds, rounds);
+
+        let mut r1 = s1.by_ruleset.remove(ruleset).unwrap_or_default();
+        let mut r2 = s2.by_ruleset.remove(ruleset).unwrap_or_default();
+        record_match(&mut r1, &mut r2, outcome, p1_rounds, p2_rounds, rounds);
+        s1.by_ruleset.insert(ruleset.to_string(), r1);
+        s2.by_ruleset.insert(ruleset.to_string(), r2);
+
+        let secs = state.duration_secs().unwrap_or_default();
+        s1.play_time_secs += secs;
+        s2.play_time_secs += secs;
+        self.players.insert(p1.to_string(), s1);
+        self.players.insert(p2.to_string(), s2);
+
+        let (first, second) = if p1 <= p2 { (p1, p2) } else { (p2, p1) };
+        let record = self
+            .head_to_head
+            .entry(first.to_string())
+            .or_default()
+            .entry(second.to_string())
+            .or_default();
+        match winner {
+            Some(w) if w == first => record.first_wins += 1,
+            Some(_) => record.second_wins += 1,
+            None => record.ties += 1,
+        }
+
+        // The computer doesn't collect achievements.
+        let Some(name) =
+            winner.filter(|_| cfg.mode != Mode::SinglePlayer || outcome == RoundWinner::Player1)
+        else {
+            return Vec::new();
+        };
+        let Some(stats) = self.players.get_mut(name) else {
+            return Vec::new();
+        };
+        earned_achievements(state, outcome, stats)
+            .into_iter()
+            .filter(|&a| stats.achievements.insert(a))
+            .map(|a| (name.to_string(), a))
+            .collect()
+    }
+
+    pub fn record_move(&mut self, name: &str, mv: Move) {
+        if matches!(mv, Move::Custom(_)) {
+            return;
+        }
+        let key = self.ensure_player(name);
+        if let Some(st) = self.players.get_mut(&key) {
+            *st.move_counts.entry(mv).or_insert(0) += 1;
+        }
+    }
+
+    // Returns (a's wins, b's wins, ties).
+    pub fn head_to_head(&self, a: &str, b: &str) -> (u32, u32, u32) {
+        let (a, b) = (self.player_key(a), self.player_key(b));
+        let (a, b) = (a.as_str(), b.as_str());
+        let (first, second, swapped) = if a <= b { (a, b, false) } else { (b, a, true) };
+        let record = self
+            .head_to_head
+            .get(first)
+            .and_then(|m| m.get(second))
+            .copied()
+            .unwrap_or_default();
+        if swapped {
+            (record.second_wins, record.first_wins, record.ties)
+        } else {
+            (record.first_wins, record.second_wins, record.ties)
+        }
+    }
+
+    pub fn record_response(&mut self, name: &str, took: Duration) {
+        let ms = took.as_millis() as u64;
+        let key = self.ensure_player(name);
+        if let Some(st) = self.players.get_mut(&key) {
+            st.responses += 1;
+            st.response_ms_total += ms;
+            st.fastest_response_ms = Some(st.fastest_response_ms.map_or(ms, |f| f.min(ms)));
+        }
+    }
+
+    // Each match of the series is already counted on its own.
+    pub fn record_series(&mut self, p1: &str, p2: &str, winner: RoundWinner) {
+        let p1 = self.ensure_player(p1);
+        let p2 = self.ensure_player(p2);
+        for (name, won) in [
+            (p1, winner == RoundWinner::Player1),
+            (p2, winner == RoundWinner::Player2),
+        ] {
+            if let Some(st) = self.players.get_mut(&name) {
+                st.series_played += 1;
+                st.series_won += u32::from(won);
+            }
+        }
+    }
+
+    pub fn challenge_result(&self, date: &str, player: &str) -> Option<&ChallengeResult> {
+        let key = self.player_key(player);
+        self.challenges.get(date)?.iter().find(|r| r.player == key)
+    }
+
+    // Only the first attempt of the day counts, so a retry is ignored.
+    pub fn record_challenge(&mut self, date: &str, state: &MatchState, winner: RoundWinner) {
+        if self.challenge_result(date, &state.config.player1).is_some() {
+            return;
+        }
+        let result = ChallengeResult {
+            player: self.player_key(&state.config.player1),
+            winner,
+            rounds_won: state.p1_round_wins,
+            rounds_lost: state.p2_round_wins,
+            rounds_played: state.history.len() as u32,
+        };
+        self.challenges
+            .entry(date.to_string())
+            .or_default()
+            .push(result);
+    }
+
+    // Best first: wins before ties before losses, then by round margin.
+    pub fn challenge_leaderboard(&self, date: &str) -> Vec<&ChallengeResult> {
+        let mut results: Vec<&ChallengeResult> = self
+            .challenges
+            .get(date)
+            .map(|v| v.iter().collect())
+            .unwrap_or_default();
+        let rank = |r: &ChallengeResult| match r.winner {
+            RoundWinner::Player1 => 0,
+            RoundWinner::Tie => 1,
+            RoundWinner::Player2 => 2,
+        };
+        results.sort_by(|a, b| {
+            rank(a)
+                .cmp(&rank(b))
+                .then(
+                    (b.rounds_won as i64 - b.rounds_lost as i64)
+                        .cmp(&(a.rounds_won as i64 - a.rounds_lost as i64)),
+                )
+                .then(a.rounds_played.cmp(&b.rounds_played))
+                .then(a.player.cmp(&b.player))
+        });
+        results
+    }
+
+    // Returns false if there was no such player.
+    pub fn remove_player(&mut self, name: &str) -> bool {
+        let key = self.player_key(name);
+        if self.players.remove(&key).is_none() {
+            return false;
+        }
+        self.head_to_head.remove(&key);
+        for opponents in self.head_to_head.values_mut() {
+            opponents.remove(&key);
+        }
+        for results in self.challenges.values_mut() {
+            results.retain(|r| r.player != key);
+        }
+        self.challenges.retain(|_, results| !results.is_empty());
+        self.head_to_head
+            .retain(|_, opponents| !opponents.is_empty());
+        true
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveData {
+    pub version: u32,
+    pub state: MatchState,
+    pub scoreboard: Scoreboard,
+}
+
+fn slot_file(slot: u32) -> String {
+    format!("{}{}.json", SAVE_SLOT_PREFIX, slot)
+}
+
+pub fn list_ruleset_files() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(RULESET_DIR) else {
+        return vec![];
+    };
+    let mut paths: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|x| x == "json"))
+        .filter_map(|p| p.to_str().map(str::to_string))
+        .collect();
+    paths.sort();
+    paths
+}
+
+pub fn list_save_slots() -> Vec<u32> {
+    let Ok(entries) = fs::read_dir(".") else {
+        return vec![];
+    };
+    let mut slots: Vec<u32> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().into_string().ok()?;
+            name.strip_prefix(SAVE_SLOT_PREFIX)?
+                .strip_suffix(".json")?
+                .parse()
+                .ok()
+        })
+        .collect();
+    slots.sort_unstable();
+    slots
+}
+
+fn next_free_slot() -> u32 {
+    let used = list_save_slots();
+    (1..).find(|n| !used.contains(n)).unwrap_or(1)
+}
+
+// Older builds kept a single save in rps_save.json; give it a slot of its own.
+pub fn migrate_legacy_save() {
+    if std::path::Path::new(SAVE_FILE).exists() {
+        let _ = fs::rename(SAVE_FILE, slot_file(next_free_slot()));
+    }
+}
+
+// A match keeps the slot it was loaded from (or first saved to), so saving
+// again overwrites its own file and never another match's.
+pub fn save_game(state: &mut MatchState, scoreboard: &Scoreboard) {
+    let slot = *state.save_slot.get_or_insert_with(next_free_slot);
+    save_game_to_slot(slot, state, scoreboard);
+}
+
+// The slot can have been taken over since the match was loaded, e.g. by the
+// same save continued further in another window.
+pub fn save_conflict(state: &MatchState) -> Option<MatchState> {
+    let json = fs::read_to_string(slot_file(state.save_slot?)).ok()?;
+    let other = parse_save(&json).ok()?.state;
+    let (ours, theirs) = (&state.config, &other.config);
+    let same_match = ours.player1 == theirs.player1
+        && ours.player2 == theirs.player2
+        && ours.ruleset.name() == theirs.ruleset.name()
+        && other.history.len() <= state.history.len()
+        && other
+            .history
+            .iter()
+            .zip(&state.history)
+            .all(|(a, b)| a.p1_move == b.p1_move && a.p2_move == b.p2_move);
+    if same_match {
+        None
+    } else {
+        Some(other)
+    }
+}
+
+fn save_game_to_slot(slot: u32, state: &MatchState, scoreboard: &Scoreboard) {
+    let data = SaveData {
+        version: SAVE_VERSION,
+        state: state.clone(),
+        scoreboard: scoreboard.clone(),
+    };
+    if let Ok(json) = serde_json::to_string_pretty(&data) {
+        let _ = fs::write(slot_file(slot), json);
+    }
+}
+
+// A save from a newer build is left alone; anything else that fails to parse
+// is moved aside so the slot doesn't get reused over it.
+pub fn load_saved_game(slot: u32) -> Result<MatchState, String> {
+    let path = slot_file(slot);
+    let data = fs::read_to_string(&path).map_err(|e| format!("Couldn't read {}: {}", path, e))?;
+    let mut sd = parse_save(&data).map_err(|e| {
+        let newer = serde_json::from_str::<serde_json::Value>(&data)
+            .ok()
+            .and_then(|v| v.get("version")?.as_u64())
+            .is_some_and(|v| v > SAVE_VERSION as u64);
+        match if newer { None } else { back_up(&path) } {
+            Some(backup) => format!("Couldn't load {}: {}; moved it to {}", path, e, backup),
+            None => format!("Couldn't load {}: {}", path, e),
+        }
+    })?;
+    sd.state.restore_rng();
+    sd.state.save_slot = Some(slot);
+    Ok(sd.state)
+}
+
+// Saves written before versioning have no version field and count as 1.
+pub fn parse_save(json: &str) -> Result<SaveData, String> {
+    let mut value: serde_json::Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let version = value.get("version").and_then(|v| v.as_u64()).unwrap_or(1) as u32;
+    if version > SAVE_VERSION {
+        return Err(format!(
+            "save version {} is newer than this build supports ({})",
+            version, SAVE_VERSION
+        ));
+    }
+    for from in version..SAVE_VERSION {
+        migrate_save(&mut value, from);
+    }
+
+    let mut sd: SaveData = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    if version < SAVE_VERSION {
+        sd.state.migrated_from = Some(version);
+    }
+    Ok(sd)
+}
+
+// Upgrades a save by one version. Fields that were only ever added carry
+// serde defaults, so v1 -> v2 just stamps the version; a future rename or
+// reshape gets its own step here before the stamp.
+fn migrate_save(value: &mut serde_json::Value, from: u32) {
+    value["version"] = (from + 1).into();
+}
+
+fn back_up(path: &str) -> Option<String> {
+    let backup = format!("{}.bak", path);
+    fs::rename(path, &backup).ok()?;
+    Some(backup)
+}
+
+pub fn clear_saved_game(slot: Option<u32>) {
+    if let Some(slot) = slot {
+        let _ = fs::remove_file(slot_file(slot));
+    }
+}
+
+// The UTC calendar date, as YYYY-MM-DD.
+pub fn utc_date(unix_secs: u64) -> String {
+    // Howard Hinnant's days-to-civil conversion.
+    let z = (unix_secs / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// The same on every machine, unlike the std hasher, so everyone gets the
+// same computer on the same day.
+pub fn challenge_seed(date: &str) -> u64 {
+    let digest = Sha256::digest(format!("rps-challenge:{}", date).as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+pub fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+pub fn seed_from_env() -> Option<u64> {
+    std::env::var(SEED_ENV).ok()?.trim().parse().ok()
+}
+
+// Scripted runs can set RPS_TIMER_MS to shorten the move timer instead of
+// waiting out whole seconds. Matches without a timer don't get one.
+pub fn move_timeout(cfg: &GameConfig) -> Option<Duration> {
+    let secs = cfg.move_timeout_secs?;
+    let ms = std::env::var(TIMER_ENV)
+        .ok()
+        .and_then(|v| v.trim().parse().ok());
+    Some(ms.map_or(Duration::from_secs(secs), Duration::from_millis))
+}
+
+// Hex SHA-256 of the nonce and the move's JSON. The nonce keeps the handful
+// of possible moves from being looked up by their hashes.
+pub fn commitment(mv: Move, nonce: &str) -> String {
+    let json = serde_json::to_string(&mv).unwrap_or_default();
+    Sha256::digest(format!("{}:{}", nonce, json).as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+pub fn verify_commitment(mv: Move, nonce: &str, hash: &str) -> bool {
+    commitment(mv, nonce) == hash
+}
+
+pub fn new_nonce(rng: &mut impl Rng) -> String {
+    (0..16)
+        .map(|_| format!("{:02x}", rng.gen::<u8>()))
+        .collect()
+}
+
+fn seeded_rng(seed: Option<u64>, offset: u64) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(offset)),
+        None => StdRng::from_entropy(),
+    }
+}
+
+// Ties during sudden death are replayed rather than recorded, so the streak
+// that triggered it stays at the tail of history. A match saved and resumed
+// mid sudden death therefore picks it straight back up from the save file.
+pub fn sudden_death(state: &MatchState) -> bool {
+    let Some(max) = state.config.max_consecutive_ties else {
+        return false;
+    };
+    let streak = state
+        .history
+        .iter()
+        .rev()
+        .take_while(|r| matches!(r.winner, RoundWinner::Tie))
+        .count();
+    streak as u32 >= max
+}
+
+pub fn tie_is_replayed(state: &MatchState) -> bool {
+    state.config.replay_ties || sudden_death(state)
+}
+
+pub fn apply_round(state: &mut MatchState, p1: Move, p2: Move, winner: RoundWinner) {
+    match winner {
+        RoundWinner::Player1 => state.p1_round_wins += 1,
+        RoundWinner::Player2 => state.p2_round_wins += 1,
+        RoundWinner::Tie => {}
+    }
+
+    state.history.push(RoundRecord {
+        round: state.round_number,
+        p1_move: p1,
+        p2_move: p2,
+        winner,
+        tiebreaker: false,
+    });
+}
+
+pub fn apply_tiebreaker(state: &mut MatchState, p1: Move, p2: Move, winner: RoundWinner) {
+    apply_round(state, p1, p2, winner);
+    if let Some(last) = state.history.last_mut() {
+        last.tiebreaker = true;
+    }
+}
+
+// None at the end of input or on a read error, which a blank line (Some
+// of just the newline) can't be confused with.
+pub fn read_input_line(reader: &mut impl BufRead) -> Option<String> {
+    let mut s = String::new();
+    match reader.read_line(&mut s) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(s),
+    }
+}
+
+// A replayed tie is logged with the number of the round it didn't count
+// toward.
+pub fn log_round(path: &Path, record: &RoundRecord, replayed: bool) -> io::Result<()> {
+    let line = serde_json::json!({
+        "round": record.round,
+        "p1_move": record.p1_move,
+        "p2_move": record.p2_move,
+        "winner": record.winner,
+        "replayed": replayed,
+        "timestamp": unix_now(),
+    });
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+// Rounds where the AI played at random made no prediction and aren't counted.
+pub fn prediction_accuracy(state: &MatchState) -> Option<(usize, usize)> {
+    let total = state.ai_predictions.len();
+    if total == 0 {
+        return None;
+    }
+    let hits = state
+        .ai_predictions
+        .iter()
+        .filter(|(predicted, actual)| predicted == actual)
+        .count();
+    Some((hits, total))
+}
+
+pub fn check_match_winner(state: &MatchState) -> Option<RoundWinner> {
+    state
+        .conceded_to
+        .or_else(|| format_winner(state))
+        .or_else(|| round_limit_winner(state))
+}
+
+pub fn ended_by_round_limit(state: &MatchState) -> bool {
+    state.conceded_to.is_none()
+        && format_winner(state).is_none()
+        && round_limit_winner(state).is_some()
+}
+
+// Replayed ties never reach history, so like tiebreakers they don't count
+// toward the limit.
+fn round_limit_winner(state: &MatchState) -> Option<RoundWinner> {
+    let max = state.config.max_rounds?;
+    let played = state.history.iter().filter(|r| !r.tiebreaker).count();
+    if (played as u32) < max {
+        return None;
+    }
+    let (p1, p2) = (state.p1_round_wins, state.p2_round_wins);
+    if p1 > p2 {
+        Some(RoundWinner::Player1)
+    } else if p2 > p1 {
+        Some(RoundWinner::Player2)
+    } else {
+        Some(RoundWinner::Tie)
+    }
+}
+
+fn format_winner(state: &MatchState) -> Option<RoundWinner> {
+    match state.config.format {
+        // Unlike BestOfN(1), which plays on through ties, a tied single
+        // round ends the match.
+        MatchFormat::SingleRound => state.history.last().map(|r| r.winner),
+        format @ (MatchFormat::BestOfN(_) | MatchFormat::FirstToK(_)) => {
+            let needed = rounds_needed(&format)?;
+            // Ties don't use up one of the N rounds. Once N decisive rounds
+            // are played without a majority (only possible for even N), the
+            // match is drawn. Replayed ties never reach history, so they
+            // change the round numbers but not the outcome.
+            let drawn = match format {
+                MatchFormat::BestOfN(n) => state.p1_round_wins + state.p2_round_wins >= n,
+                _ => false,
+            };
+            let (p1, p2) = (state.p1_round_wins, state.p2_round_wins);
+            if p1 >= needed && p2 >= needed {
+                Some(first_to_reach(state, needed))
+            } else if p1 >= needed {
+                Some(RoundWinner::Player1)
+            } else if p2 >= needed {
+                Some(RoundWinner::Player2)
+            } else if drawn {
+                Some(RoundWinner::Tie)
+            } else {
+                None
+            }
+        }
+        MatchFormat::FirstToKWinBy2(k) => {
+            let (p1, p2) = (state.p1_round_wins, state.p2_round_wins);
+            if p1 >= k && p1 >= p2 + 2 {
+                Some(RoundWinner::Player1)
+            } else if p2 >= k && p2 >= p1 + 2 {
+                Some(RoundWinner::Player2)
+            } else {
+                None
+            }
+        }
+        MatchFormat::Points { target } => {
+            // A tie can carry both players over the target at once; the
+            // higher total still wins and level totals tie the match.
+            let (p1, p2) = match_points(state);
+            if p1 < target && p2 < target {
+                None
+            } else if p1 > p2 {
+                Some(RoundWinner::Player1)
+            } else if p2 > p1 {
+                Some(RoundWinner::Player2)
+            } else {
+                Some(RoundWinner::Tie)
+            }
+        }
+    }
+}
+
+// First to `target` match wins; drawn matches are counted but don't bring
+// either side closer.
+#[derive(Debug, Clone)]
+pub struct Series {
+    pub target: u32,
+    pub p1_wins: u32,
+    pub p2_wins: u32,
+    pub ties: u32,
+}
+
+impl Series {
+    pub fn new(target: u32) -> Self {
+        Self {
+            target,
+            p1_wins: 0,
+            p2_wins: 0,
+            ties: 0,
+        }
+    }
+
+    pub fn played(&self) -> u32 {
+        self.p1_wins + self.p2_wins + self.ties
+    }
+
+    pub fn record(&mut self, match_winner: RoundWinner) {
+        match match_winner {
+            RoundWinner::Player1 => self.p1_wins += 1,
+            RoundWinner::Player2 => self.p2_wins += 1,
+            RoundWinner::Tie => self.ties += 1,
+        }
+    }
+
+    pub fn winner(&self) -> Option<RoundWinner> {
+        if self.p1_wins >= self.target {
+            Some(RoundWinner::Player1)
+        } else if self.p2_wins >= self.target {
+            Some(RoundWinner::Player2)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Standing {
+    pub name: String,
+    pub played: u32,
+    pub wins: u32,
+    pub ties: u32,
+    pub losses: u32,
+    pub rounds_won: u32,
+}
+
+impl Standing {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            played: 0,
+            wins: 0,
+            ties: 0,
+            losses: 0,
+            rounds_won: 0,
+        }
+    }
+}
+
+// A round robin between sessions. Pairings index into players and are played
+// in order; the one in progress when the tournament was left starts over.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TournamentState {
+    pub players: Vec<String>,
+    pub ruleset: Ruleset,
+    pub best_of: u32,
+    pub use_color: bool,
+    pub show_ascii: bool,
+    pub show_emoji: bool,
+    pub pairings: Vec<(usize, usize)>,
+    pub completed: Vec<bool>,
+    pub standings: Vec<Standing>,
+}
+
+impl TournamentState {
+    pub fn new(players: Vec<String>, ruleset: Ruleset, best_of: u32) -> Self {
+        let mut pairings = Vec::new();
+        for i in 0..players.len() {
+            for j in i + 1..players.len() {
+                pairings.push((i, j));
+            }
+        }
+        Self {
+            standings: players.iter().map(|n| Standing::new(n)).collect(),
+            completed: vec![false; pairings.len()],
+            players,
+            ruleset,
+            best_of,
+            use_color: true,
+            show_ascii: true,
+            show_emoji: false,
+            pairings,
+        }
+    }
+
+    pub fn completed_count(&self) -> usize {
+        self.completed.iter().filter(|&&done| done).count()
+    }
+
+    pub fn load() -> Option<Self> {
+        let data = fs::read_to_string(TOURNAMENT_FILE).ok()?;
+        serde_json::from_str(&data).ok()
+    }
+
+    pub fn save(&self) {
+        if let Ok(json) = serde_json::to_string_pretty(self) {
+            let _ = fs::write(TOURNAMENT_FILE, json);
+        }
+    }
+
+    pub fn clear() {
+        let _ = fs::remove_file(TOURNAMENT_FILE);
+    }
+}
+
+// Only a state that kept being played after the match was decided has both
+// sides over the line; whoever got there first in history won it.
+fn first_to_reach(state: &MatchState, needed: u32) -> RoundWinner {
+    let (mut p1, mut p2) = state.config.handicap;
+    for r in &state.history {
+        if p1 >= needed || p2 >= needed {
+            break;
+        }
+        match r.winner {
+            RoundWinner::Player1 => p1 += 1,
+            RoundWinner::Player2 => p2 += 1,
+            RoundWinner::Tie => {}
+        }
+    }
+    if p2 >= needed && p1 < needed {
+        RoundWinner::Player2
+    } else {
+        RoundWinner::Player1
+    }
+}
+
+// Round wins that take the match, for the formats decided by round wins.
+pub fn rounds_needed(format: &MatchFormat) -> Option<u32> {
+    match *format {
+        MatchFormat::BestOfN(n) => Some(n / 2 + 1),
+        MatchFormat::FirstToK(k) | MatchFormat::FirstToKWinBy2(k) => Some(k),
+        MatchFormat::SingleRound | MatchFormat::Points { .. } => None,
+    }
+}
+
+// Round wins each player still needs. Under win-by-2 the target moves up
+// with the other player's score.
+pub fn wins_to_go(state: &MatchState) -> Option<(u32, u32)> {
+    let needed = rounds_needed(&state.config.format)?;
+    let (p1, p2) = (state.p1_round_wins, state.p2_round_wins);
+    let (n1, n2) = match state.config.format {
+        MatchFormat::FirstToKWinBy2(_) => (needed.max(p2 + 2), needed.max(p1 + 2)),
+        _ => (needed, needed),
+    };
+    Some((n1.saturating_sub(p1), n2.saturating_sub(p2)))
+}
+
+// Whether each player would take the match by winning the next round.
+pub fn match_point(state: &MatchState) -> (bool, bool) {
+    match wins_to_go(state) {
+        Some((p1, p2)) if check_match_winner(state).is_none() => (p1 <= 1, p2 <= 1),
+        _ => (false, false),
+    }
+}
+
+// The round after which the winner couldn't be caught. None for single
+// rounds, where there's only one, and for drawn matches.
+pub fn decisive_round(state: &MatchState) -> Option<&RoundRecord> {
+    let winner = check_match_winner(state).filter(|&w| w != RoundWinner::Tie)?;
+    let (h1, h2) = state.config.handicap;
+    let mut scores = match state.config.format {
+        MatchFormat::SingleRound => return None,
+        MatchFormat::Points { .. } => (h1 * 3, h2 * 3),
+        _ => (h1, h2),
+    };
+    state.history.iter().find(|r| {
+        let (won, tied) = match state.config.format {
+            MatchFormat::Points { .. } => (3, 1),
+            _ => (1, 0),
+        };
+        match r.winner {
+            RoundWinner::Player1 => scores.0 += won,
+            RoundWinner::Player2 => scores.1 += won,
+            RoundWinner::Tie => {
+                scores.0 += tied;
+                scores.1 += tied;
+            }
+        }
+        let (ours, theirs) = match winner {
+            RoundWinner::Player1 => scores,
+            _ => (scores.1, scores.0),
+        };
+        match state.config.format {
+            MatchFormat::Points { target } => ours >= target && ours > theirs,
+            MatchFormat::FirstToKWinBy2(k) => ours >= k && ours >= theirs + 2,
+            format => rounds_needed(&format).is_some_and(|needed| ours >= needed),
+        }
+    })
+}
+
+// A handicap round is worth the same as a won one.
+pub fn match_points(state: &MatchState) -> (u32, u32) {
+    let (h1, h2) = state.config.handicap;
+    state
+        .history
+        .iter()
+        .fold((h1 * 3, h2 * 3), |(p1, p2), r| match r.winner {
+            RoundWinner::Player1 => (p1 + 3, p2),
+            RoundWinner::Player2 => (p1, p2 + 3),
+            RoundWinner::Tie => (p1 + 1, p2 + 1),
+        })
+}
+
+// Rejects a handicap that would decide the match before it starts.
+pub fn valid_handicap(format: &MatchFormat, (p1, p2): (u32, u32)) -> bool {
+    match *format {
+        MatchFormat::SingleRound => p1 == 0 && p2 == 0,
+        MatchFormat::BestOfN(n) => p1 <= n / 2 && p2 <= n / 2 && p1 + p2 < n,
+        MatchFormat::FirstToK(k) | MatchFormat::FirstToKWinBy2(k) => p1 < k && p2 < k,
+        MatchFormat::Points { target } => p1 * 3 < target && p2 * 3 < target,
+    }
+}
+
+// Points matches show points; every other format shows round wins.
+pub fn displayed_score(state: &MatchState) -> (u32, u32) {
+    match state.config.format {
+        MatchFormat::Points { .. } => match_points(state),
+        _ => (state.p1_round_wins, state.p2_round_wins),
+    }
+}
+
+// (win, loss, tie) chances of a round for player 1 when both sides pick
+// uniformly at random.
+pub fn random_round_odds(ruleset: &Ruleset) -> (f64, f64, f64) {
+    let all = Move::all_for_ruleset(ruleset);
+    let mut counts = (0, 0, 0);
+    for &a in &all {
+        for &b in &all {
+            match decide_winner(ruleset, a, b) {
+                RoundWinner::Player1 => counts.0 += 1,
+                RoundWinner::Player2 => counts.1 += 1,
+                RoundWinner::Tie => counts.2 += 1,
+            }
+        }
+    }
+    let total = (all.len() * all.len()).max(1) as f64;
+    (
+        counts.0 as f64 / total,
+        counts.1 as f64 / total,
+        counts.2 as f64 / total,
+    )
+}
+
+// The same odds as they've actually gone in this match so far.
+pub fn observed_round_odds(history: &[RoundRecord]) -> Option<(f64, f64, f64)> {
+    if history.is_empty() {
+        return None;
+    }
+    let total = history.len() as f64;
+    let share = |w: RoundWinner| history.iter().filter(|r| r.winner == w).count() as f64 / total;
+    Some((
+        share(RoundWinner::Player1),
+        share(RoundWinner::Player2),
+        share(RoundWinner::Tie),
+    ))
+}
+
+// Chances of each player taking the match from `score` (round wins, or
+// points for a points match) when every round goes by `odds`. Whatever is
+// left over is a drawn match. Sudden death isn't modelled.
+pub fn win_probability(
+    format: MatchFormat,
+    score: (u32, u32),
+    odds: (f64, f64, f64),
+) -> (f64, f64) {
+    fn go(
+        format: MatchFormat,
+        score: (u32, u32),
+        odds: (f64, f64, f64),
+        memo: &mut HashMap<(u32, u32), (f64, f64)>,
+    ) -> (f64, f64) {
+        let (s1, s2) = score;
+        let decided = match format {
+            MatchFormat::SingleRound => unreachable!(),
+            MatchFormat::BestOfN(n) => {
+                let needed = n / 2 + 1;
+                if s1 >= needed {
+                    Some((1.0, 0.0))
+                } else if s2 >= needed {
+                    Some((0.0, 1.0))
+                } else if s1 + s2 >= n {
+                    Some((0.0, 0.0))
+                } else {
+                    None
+                }
+            }
+            MatchFormat::FirstToK(k) => match (s1 >= k, s2 >= k) {
+                (true, _) => Some((1.0, 0.0)),
+                (_, true) => Some((0.0, 1.0)),
+                _ => None,
+            },
+            MatchFormat::FirstToKWinBy2(k) if s1 >= k && s1 >= s2 + 2 => Some((1.0, 0.0)),
+            MatchFormat::FirstToKWinBy2(k) if s2 >= k && s2 >= s1 + 2 => Some((0.0, 1.0)),
+            // Deuce goes around in circles, so it's worked out in closed
+            // form: from level, two wins in a row before two losses.
+            MatchFormat::FirstToKWinBy2(k) if s1 + 1 >= k && s2 + 1 >= k => {
+                let (win, loss, _) = odds;
+                let p = win / (win + loss);
+                let level = p * p / (p * p + (1.0 - p) * (1.0 - p));
+                let p1 = match s1.cmp(&s2) {
+                    std::cmp::Ordering::Greater => p + (1.0 - p) * level,
+                    std::cmp::Ordering::Less => p * level,
+                    std::cmp::Ordering::Equal => level,
+                };
+                Some((p1, 1.0 - p1))
+            }
+            MatchFormat::FirstToKWinBy2(_) => None,
+            MatchFormat::Points { target } if s1 >= target || s2 >= target => {
+                Some(match s1.cmp(&s2) {
+                    std::cmp::Ordering::Greater => (1.0, 0.0),
+                    std::cmp::Ordering::Less => (0.0, 1.0),
+                    std::cmp::Ordering::Equal => (0.0, 0.0),
+                })
+            }
+            MatchFormat::Points { .. } => None,
+        };
+        if let Some(result) = decided {
+            return result;
+        }
+        if let Some(&result) = memo.get(&score) {
+            return result;
+        }
+
+        let (win, loss, tie) = odds;
+        let result = match format {
+            MatchFormat::Points { .. } => {
+                let a = go(format, (s1 + 3, s2), odds, memo);
+                let b = go(format, (s1, s2 + 3), odds, memo);
+                let c = go(format, (s1 + 1, s2 + 1), odds, memo);
+                (
+                    win * a.0 + loss * b.0 + tie * c.0,
+                    win * a.1 + loss * b.1 + tie * c.1,
+                )
+            }
+            // Ties change nothing here, so only decisive rounds count.
+            _ => {
+                let p = win / (win + loss);
+                let a = go(format, (s1 + 1, s2), odds, memo);
+                let b = go(format, (s1, s2 + 1), odds, memo);
+                (p * a.0 + (1.0 - p) * b.0, p * a.1 + (1.0 - p) * b.1)
+            }
+        };
+        memo.insert(score, result);
+        result
+    }
+
+    let (win, loss, _) = odds;
+    match format {
+        MatchFormat::SingleRound => (win, loss),
+        MatchFormat::BestOfN(_) | MatchFormat::FirstToK(_) | MatchFormat::FirstToKWinBy2(_)
+            if win + loss <= 0.0 =>
+        {
+            (0.0, 0.0)
+        }
+        _ => go(format, score, odds, &mut HashMap::new()),
+    }
+}
+
+pub fn parse_move(input: &str, ruleset: &Ruleset) -> Option<Move> {
+    if let Ruleset::Custom(c) = ruleset {
+        let t = input.trim();
+        let idx = match t.parse::<usize>() {
+            Ok(n) if n >= 1 && n <= c.moves.len() => Some(n - 1),
+            _ => c.index_of(t),
+        };
+        return idx.map(|i| Move::Custom(i as u8));
+    }
+
+    match input.trim().to_lowercase().as_str() {
+        "rock" | "r" => Some(Move::Rock),
+        "paper" | "p" => Some(Move::Paper),
+        "scissors" | "s" => Some(Move::Scissors),
+        "lizard" | "l" if matches!(ruleset, Ruleset::Extended) => Some(Move::Lizard),
+        "spock" | "k" if matches!(ruleset, Ruleset::Extended) => Some(Move::Spock),
+        _ => None,
+    }
+}
+
+// Extra spellings for moves, lowercase alias -> move name as typed in a strict
+// parse ("piedra" -> "rock", or a custom ruleset's own move name).
+pub type MoveAliases = HashMap<String, String>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveMatch {
+    Found(Move),
+    Ambiguous(Vec<Move>),
+    NotFound,
+}
+
+// A missing or unreadable file just means no user aliases.
+pub fn load_aliases() -> MoveAliases {
+    let Ok(data) = fs::read_to_string(ALIAS_FILE) else {
+        return MoveAliases::new();
+    };
+    let raw: MoveAliases = serde_json::from_str(&data).unwrap_or_default();
+    raw.into_iter()
+        .map(|(alias, name)| (alias.trim().to_lowercase(), name))
+        .collect()
+}
+
+const FUZZY_MIN_LEN: usize = 3;
+
+// The strict parse goes first, so single letters and exact names never
+// reach the fuzzy step. Then aliases, then anything one edit away from a move
+// name or alias. Fuzzy matching skips very short input, where one edit could
+// turn it into almost anything.
+pub fn match_move(input: &str, ruleset: &Ruleset, aliases: &MoveAliases) -> MoveMatch {
+    if let Some(mv) = parse_move(input, ruleset) {
+        return MoveMatch::Found(mv);
+    }
+    let typed = input.trim().to_lowercase();
+    if let Some(mv) = aliases.get(&typed).and_then(|n| parse_move(n, ruleset)) {
+        return MoveMatch::Found(mv);
+    }
+    if typed.chars().count() < FUZZY_MIN_LEN {
+        return MoveMatch::NotFound;
+    }
+
+    let names = Move::all_for_ruleset(ruleset)
+        .into_iter()
+        .map(|m| (ruleset.move_name(m).to_lowercase(), m));
+    let alias_targets = aliases
+        .iter()
+        .filter_map(|(alias, name)| Some((alias.clone(), parse_move(name, ruleset)?)));
+    let mut close: Vec<Move> = Vec::new();
+    for (name, mv) in names.chain(alias_targets) {
+        if levenshtein(&typed, &name) <= 1 && !close.contains(&mv) {
+            close.push(mv);
+        }
+    }
+    let order = Move::all_for_ruleset(ruleset);
+    close.sort_by_key(|m| order.iter().position(|o| o == m));
+
+    match close.len() {
+        0 => MoveMatch::NotFound,
+        1 => MoveMatch::Found(close[0]),
+        _ => MoveMatch::Ambiguous(close),
+    }
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            row.push((prev[j] + cost).min(prev[j + 1] + 1).min(row[j] + 1));
+        }
+        prev = row;
+    }
+    prev[b.len()]
+}
+
+/// Decides a round between `p1` and `p2`.
+///
+/// Identical moves tie, and so do two moves a custom ruleset leaves
+/// unrelated.
+///
+/// ```
+/// use rps_game::{decide_winner, Move, RoundWinner, Ruleset};
+///
+/// use Move::*;
+/// assert_eq!(decide_winner(&Ruleset::Classic, Rock, Scissors), RoundWinner::Player1);
+/// assert_eq!(decide_winner(&Ruleset::Classic, Rock, Paper), RoundWinner::Player2);
+/// assert_eq!(decide_winner(&Ruleset::Classic, Rock, Rock), RoundWinner::Tie);
+/// ```
+pub fn decide_winner(ruleset: &Ruleset, p1: Move, p2: Move) -> RoundWinner {
+    if p1 == p2 {
+        return RoundWinner::Tie;
+    }
+
+    // A custom table may leave some pairs unrelated; those count as ties.
+    if beats(ruleset, p1, p2) {
+        RoundWinner::Player1
+    } else if beats(ruleset, p2, p1) {
+        RoundWinner::Player2
+    } else {
+        RoundWinner::Tie
+    }
+}
+
+/// Whether `a` beats `b` under `ruleset`. A move that isn't part of the
+/// ruleset beats nothing.
+///
+/// ```
+/// use rps_game::{beats, Move, Ruleset};
+///
+/// assert!(beats(&Ruleset::Extended, Move::Lizard, Move::Spock));
+/// assert!(!beats(&Ruleset::Extended, Move::Spock, Move::Lizard));
+/// assert!(!beats(&Ruleset::Classic, Move::Lizard, Move::Paper));
+/// ```
+pub fn beats(ruleset: &Ruleset, a: Move, b: Move) -> bool {
+    match ruleset {
+        Ruleset::Classic => classic_beats(a, b),
+        Ruleset::Extended => extended_beats(a, b),
+        Ruleset::Custom(c) => match (a, b) {
+            (Move::Custom(a), Move::Custom(b)) => c.beats(a as usize, b as usize),
+            _ => false,
+        },
+    }
+}
+
+pub type BeatsTable = HashMap<Move, Vec<Move>>;
+
+#[derive(Debug)]
+pub enum RulesetError {
+    Contradiction(Move, Move),
+    Unbalanced {
+        mv: Move,
+        wins: usize,
+        losses: usize,
+        reference: Move,
+        expected_wins: usize,
+        expected_losses: usize,
+    },
+}
+
+impl RulesetError {
+    pub fn describe(&self, ruleset: &Ruleset) -> String {
+        match *self {
+            RulesetError::Contradiction(a, b) => format!(
+                "{} and {} are both listed as beating each other.",
+                ruleset.move_name(a),
+                ruleset.move_name(b)
+            ),
+            RulesetError::Unbalanced {
+                mv,
+                wins,
+                losses,
+                reference,
+                expected_wins,
+                expected_losses,
+            } => format!(
+                "Unbalanced ruleset: {} beats {} and loses to {}, but {} beats {} and loses to {}.",
+                ruleset.move_name(mv),
+                wins,
+                losses,
+                ruleset.move_name(reference),
+                expected_wins,
+                expected_losses
+            ),
+        }
+    }
+}
+
+pub fn beats_table(ruleset: &Ruleset) -> BeatsTable {
+    let all = Move::all_for_ruleset(ruleset);
+    all.iter()
+        .map(|&a| {
+            let beaten = all
+                .iter()
+                .copied()
+                .filter(|&b| beats(ruleset, a, b))
+                .collect();
+            (a, beaten)
+        })
+        .collect()
+}
+
+pub fn check_ruleset(ruleset: &Ruleset) -> Result<(), RulesetError> {
+    validate_ruleset(&Move::all_for_ruleset(ruleset), &beats_table(ruleset))
+}
+
+// Balanced means every move beats the same number of moves and loses to the
+// same number. The most common (wins, losses) shape is taken as the expected
+// one (earliest move wins ties), and the first move that differs is reported.
+pub fn validate_ruleset(moves: &[Move], beats: &BeatsTable) -> Result<(), RulesetError> {
+    let beaten_by = |a: Move, b: Move| beats.get(&a).is_some_and(|l| l.contains(&b));
+
+    for (i, &a) in moves.iter().enumerate() {
+        for &b in &moves[i + 1..] {
+            if beaten_by(a, b) && beaten_by(b, a) {
+                return Err(RulesetError::Contradiction(a, b));
+            }
+        }
+    }
+
+    let shapes: Vec<(usize, usize)> = moves
+        .iter()
+        .map(|&m| {
+            let wins = moves.iter().filter(|&&o| beaten_by(m, o)).count();
+            let losses = moves.iter().filter(|&&o| beaten_by(o, m)).count();
+            (wins, losses)
+        })
+        .collect();
+
+    let mut reference = 0;
+    let mut best = 0;
+    for (i, shape) in shapes.iter().enumerate() {
+        let count = shapes.iter().filter(|s| *s == shape).count();
+        if count > best {
+            best = count;
+            reference = i;
+        }
+    }
+
+    for (&mv, &(wins, losses)) in moves.iter().zip(&shapes) {
+        let (expected_wins, expected_losses) = shapes[reference];
+        if wins != expected_wins || losses != expected_losses {
+            return Err(RulesetError::Unbalanced {
+                mv,
+                wins,
+                losses,
+                reference: moves[reference],
+                expected_wins,
+                expected_losses,
+            });
+        }
+    }
+    Ok(())
+}
+
+// A pair where neither move beats the other always ties. The loader allows
+// that, but the ruleset editor only saves complete tables.
+pub fn undecided_pair(moves: &[Move], beats: &BeatsTable) -> Option<(Move, Move)> {
+    let beaten_by = |a: Move, b: Move| beats.get(&a).is_some_and(|l| l.contains(&b));
+    moves.iter().enumerate().find_map(|(i, &a)| {
+        moves[i + 1..]
+            .iter()
+            .find(|&&b| !beaten_by(a, b) && !beaten_by(b, a))
+            .map(|&b| (a, b))
+    })
+}
+
+fn classic_beats(a: Move, b: Move) -> bool {
+    matches!(
+        (a, b),
+        (Move::Rock, Move::Scissors) | (Move::Paper, Move::Rock) | (Move::Scissors, Move::Paper)
+    )
+}
+
+fn extended_beats(a: Move, b: Move) -> bool {
+    matches!(
+        (a, b),
+        (Move::Rock, Move::Scissors)
+            | (Move::Rock, Move::Lizard)
+            | (Move::Paper, Move::Rock)
+            | (Move::Paper, Move::Spock)
+            | (Move::Scissors, Move::Paper)
+            | (Move::Scissors, Move::Lizard)
+            | (Move::Lizard, Move::Spock)
+            | (Move::Lizard, Move::Paper)
+            | (Move::Spock, Move::Scissors)
+            | (Move::Spock, Move::Rock)
+    )
+}
+
+// What the AI played, and for Hard, what it expected and what gave it away.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AiDecision {
+    pub chosen: Move,
+    pub predicted: Option<Move>,
+    pub rationale: Option<Rationale>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rationale {
+    Cycle { period: usize },
+    Frequency { count: usize, out_of: usize },
+}
+
+pub fn ai_move(state: &mut MatchState, human_move: Move) -> AiDecision {
+    remember_human_move(state, human_move);
+    choose_ai_move(state, Some(human_move))
+}
+
+pub fn remember_human_move(state: &mut MatchState, mv: Move) {
+    // Clamped here too, since a save file may have been edited by hand.
+    let memory = state.config.ai_memory.clamp(1, MAX_AI_MEMORY);
+    state.human_recent.push(mv);
+    while state.human_recent.len() > memory {
+        state.human_recent.remove(0);
+    }
+}
+
+// `peek` is the human's move this round, already remembered; Normal sometimes
+// cheats with it. Without it, as when two AIs play, every level goes on
+// earlier rounds only.
+pub fn choose_ai_move(state: &mut MatchState, peek: Option<Move>) -> AiDecision {
+    let memory = state.config.ai_memory.clamp(1, MAX_AI_MEMORY);
+    let rules = state.config.ruleset.clone();
+    let all = Move::all_for_ruleset(&rules);
+    let diff = state.config.difficulty.unwrap_or(Difficulty::Easy);
+
+    // Guesses are made from the moves before this one. A level that falls
+    // back on `peek` knew the answer, so that round isn't a prediction.
+    let before = match peek {
+        Some(_) => &state.human_recent[..state.human_recent.len().saturating_sub(1)],
+        None => &state.human_recent[..],
+    };
+    let mut peeked = false;
+    let mut rationale = None;
+    let predicted = match diff {
+        Difficulty::Easy => None,
+        Difficulty::Normal => {
+            let chance = state.config.counter_chance.min(100);
+            let roll: u8 = state.rng.gen_range(0..100);
+            if roll < 100 - chance {
+                None
+            } else {
+                peeked = peek.is_some();
+                peek
+            }
+        }
+        Difficulty::Hard => {
+            if let Some(cycle) = detect_cycle(before) {
+                rationale = Some(Rationale::Cycle {
+                    period: cycle.len(),
+                });
+                Some(cycle[0])
+            } else {
+                let common = if state.config.weight_recent {
+                    weighted_prediction(before, RECENCY_DECAY)
+                } else {
+                    most_common(before)
+                };
+                rationale = common.map(|m| Rationale::Frequency {
+                    count: before.iter().filter(|&&h| h == m).count(),
+                    out_of: before.len(),
+                });
+                peeked = common.is_none() && peek.is_some();
+                common.or(peek)
+            }
+        }
+        Difficulty::Expert => {
+            let guess = markov_prediction(&state.history).or_else(|| most_common(before));
+            peeked = guess.is_none() && peek.is_some();
+            guess.or(peek)
+        }
+        Difficulty::Adaptive => overdue_prediction(before, &all, &mut state.rng),
+        // Plays whatever beats the human's previous move; random on the first round.
+        Difficulty::Mirror => state.history.last().map(|r| r.p1_move),
+    };
+
+    let mut mv = match predicted {
+        Some(p) => {
+            if let Some(human_move) = peek.filter(|_| !peeked) {
+                state.ai_predictions.push((p, human_move));
+            }
+            best_counter(&rules, p)
+        }
+        None => random_from(&all, &mut state.rng),
+    };
+
+    // Don't repeat a move that just lost. Another counter to the prediction
+    // is preferred, then any other move; a ruleset with nothing else to play
+    // keeps the original choice. The move comes from the round itself, since
+    // a replayed tie leaves no record but is still in ai_recent.
+    let just_lost = state
+        .history
+        .last()
+        .filter(|r| matches!(r.winner, RoundWinner::Player1))
+        .map(|r| r.p2_move);
+    // Mirror always plays the counter, even one that just lost.
+    if just_lost == Some(mv) && !matches!(diff, Difficulty::Mirror) {
+        let others: Vec<Move> = all.iter().copied().filter(|&m| m != mv).collect();
+        let counters: Vec<Move> = match predicted {
+            Some(p) => others
+                .iter()
+                .copied()
+                .filter(|&m| beats(&rules, m, p))
+                .collect(),
+            None => vec![],
+        };
+        if !counters.is_empty() {
+            mv = random_from(&counters, &mut state.rng);
+        } else if !others.is_empty() {
+            mv = random_from(&others, &mut state.rng);
+        }
+    }
+
+    state.ai_recent.push(mv);
+    while state.ai_recent.len() > memory {
+        state.ai_recent.remove(0);
+    }
+    AiDecision {
+        chosen: mv,
+        predicted,
+        rationale,
+    }
+}
+
+const MARKOV_MIN_TRANSITIONS: usize = 3;
+const CYCLE_MIN_REPEATS: usize = 3;
+
+// Looks for the shortest period of 2 to 4 moves that the last
+// CYCLE_MIN_REPEATS * period moves follow exactly. A period of one repeated
+// move isn't a cycle and is left to most_common. The period comes back
+// rotated so its first element is the move expected next.
+pub fn detect_cycle(recent: &[Move]) -> Option<Vec<Move>> {
+    (2..=4).find_map(|period| {
+        let window = period * CYCLE_MIN_REPEATS;
+        if recent.len() < window {
+            return None;
+        }
+        let tail = &recent[recent.len() - window..];
+        let repeats = (period..window).all(|i| tail[i] == tail[i - period]);
+        let cycle = &tail[window - period..];
+        let varied = cycle.iter().any(|&m| m != cycle[0]);
+        (repeats && varied).then(|| cycle.to_vec())
+    })
+}
+
+// First-order model over the human's moves: how often Y followed X.
+// Rebuilt from history every round so it survives save/load for free.
+fn markov_prediction(history: &[RoundRecord]) -> Option<Move> {
+    let last = history.last()?.p1_move;
+
+    let followers: Vec<Move> = history
+        .windows(2)
+        .filter(|pair| pair[0].p1_move == last)
+        .map(|pair| pair[1].p1_move)
+        .collect();
+
+    if followers.len() < MARKOV_MIN_TRANSITIONS {
+        return None;
+    }
+    most_common(&followers)
+}
+
+// Counts of each move in `recent`, in the order of `moves`, zeros included.
+pub fn move_distribution(recent: &[Move], moves: &[Move]) -> Vec<(Move, usize)> {
+    moves
+        .iter()
+        .map(|&m| (m, recent.iter().filter(|&&r| r == m).count()))
+        .collect()
+}
+
+// Guesses the human will mix things up with a move they've been neglecting:
+// each move is weighted by how far it trails the most played one, plus one
+// so nothing is ruled out. Sampling walks `moves` in order, so a seeded rng
+// gives the same pick every time. With nothing played yet there's nothing
+// to go on.
+fn overdue_prediction(recent: &[Move], moves: &[Move], rng: &mut StdRng) -> Option<Move> {
+    if recent.is_empty() {
+        return None;
+    }
+    let dist = move_distribution(recent, moves);
+    let most = dist.iter().map(|&(_, c)| c).max()?;
+    let weights: Vec<(Move, usize)> = dist.iter().map(|&(m, c)| (m, most - c + 1)).collect();
+
+    let mut roll = rng.gen_range(0..weights.iter().map(|&(_, w)| w).sum::<usize>());
+    for (m, w) in weights {
+        if roll < w {
+            return Some(m);
+        }
+        roll -= w;
+    }
+    None
+}
+
+const ROCK_ART: &str = r#"
+    _______
+---'   ____)
+      (_____)
+      (_____)
+      (____)
+---.__(___)
+"#;
+
+const PAPER_ART: &str = r#"
+     _______
+---'   ____)____
+          ______)
+          _______)
+         _______)
+---.__________)
+"#;
+
+const SCISSORS_ART: &str = r#"
+    _______
+---'   ____)____
+          ______)
+       __________)
+      (____)
+---.__(___)
+"#;
+
+const LIZARD_ART: &str = r#"
+     __,---._
+    /        `.
+   |   .-"""-. |
+   |  /  _ _  \|
+    \ | | | | |
+     \| |_| |_||
+       \       /
+        `-.__.-'
+"#;
+
+const SPOCK_ART: &str = r#"
+    _  _    _  _
+   | || |  | || |
+   | || |  | || |
+   | || \__/ || |
+ _ |  \      /  |
+( \|   `----'   |
+ \              /
+  \            /
+   |          |
+ Live long and prosper
+"#;
+
+// Art from load_art. It's leaked so ascii_move can hand it out like the
+// built-in art, which is fine for something loaded once or twice a run.
+static CUSTOM_ART: RwLock<Option<HashMap<Move, &'static str>>> = RwLock::new(None);
+
+#[derive(Debug)]
+pub enum ArtError {
+    Empty,
+    // ASCII mode is for terminals that can't be trusted with anything else.
+    NotAscii,
+    Read(io::Error),
+}
+
+// Replaces whatever was loaded before. A move without a file keeps the
+// built-in art; files that are there but can't be used are returned so the
+// caller can say so, and the built-in art is used for those too.
+pub fn load_art(dir: &Path) -> Vec<(PathBuf, ArtError)> {
+    let mut art = HashMap::new();
+    let mut problems = vec![];
+    for mv in Move::all_for_ruleset(&Ruleset::Extended) {
+        let name = Ruleset::Extended.move_name(mv).to_lowercase();
+        let path = dir.join(format!("{}.txt", name));
+        match fs::read_to_string(&path) {
+            Ok(text) if text.trim().is_empty() => problems.push((path, ArtError::Empty)),
+            Ok(text) if !text.is_ascii() => problems.push((path, ArtError::NotAscii)),
+            // Framed by blank lines like the built-in art.
+            Ok(text) => {
+                let lines: Vec<&str> = text.lines().skip_while(|l| l.trim().is_empty()).collect();
+                let text = format!("\n{}\n", lines.join("\n").trim_end());
+                art.insert(mv, &*Box::leak(text.into_boxed_str()));
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => problems.push((path, ArtError::Read(e))),
+        }
+    }
+    if let Ok(mut custom) = CUSTOM_ART.write() {
+        *custom = Some(art);
+    }
+    problems
+}
+
+// Plain ASCII, for terminals that can't show emoji; load_art turns away
+// anything else.
+pub fn ascii_move(mv: Move) -> &'static str {
+    let custom = CUSTOM_ART
+        .read()
+        .ok()
+        .and_then(|art| art.as_ref()?.get(&mv).copied());
+    if let Some(art) = custom {
+        return art;
+    }
+    match mv {
+        Move::Rock => ROCK_ART,
+        Move::Paper => PAPER_ART,
+        Move::Scissors => SCISSORS_ART,
+        Move::Lizard => LIZARD_ART,
+        Move::Spock => SPOCK_ART,
+        Move::Custom(_) => "",
+    }
+}
+
+pub fn random_from(list: &[Move], rng: &mut StdRng) -> Move {
+    let idx = rng.gen_range(0..list.len());
+    list[idx]
+}
+
+// Like most_common, but the latest move counts 1 and each one before it
+// `decay` times the one after, so a switch shows up within a few rounds.
+// Equal weights go to whichever move was played more recently.
+fn weighted_prediction(recent: &[Move], decay: f64) -> Option<Move> {
+    let mut weights: Vec<(Move, f64)> = vec![];
+    let mut weight = 1.0;
+    for &m in recent.iter().rev() {
+        match weights.iter_mut().find(|(seen, _)| *seen == m) {
+            Some((_, total)) => *total += weight,
+            None => weights.push((m, weight)),
+        }
+        weight *= decay;
+    }
+    weights
+        .into_iter()
+        .reduce(|best, next| if next.1 > best.1 { next } else { best })
+        .map(|(m, _)| m)
+}
+
+// Ties go to whichever move was played more recently, so a seeded match
+// comes out the same every time.
+pub fn most_common(list: &[Move]) -> Option<Move> {
+    let mut freq: Vec<(Move, usize)> = vec![];
+    for &m in list.iter().rev() {
+        match freq.iter_mut().find(|(seen, _)| *seen == m) {
+            Some((_, count)) => *count += 1,
+            None => freq.push((m, 1)),
+        }
+    }
+    freq.into_iter()
+        .reduce(|best, next| if next.1 > best.1 { next } else { best })
+        .map(|(m, _)| m)
+}
+
+// Of the moves that beat the target, prefer the one the fewest moves beat
+// (the hardest to punish); remaining ties go to the earliest move in
+// ruleset order (Rock, Paper, Scissors, Lizard, Spock, then custom moves as
+// listed). With nothing beating the target the AI settles for a tie.
+pub fn best_counter(ruleset: &Ruleset, target: Move) -> Move {
+    let all = Move::all_for_ruleset(ruleset);
+    all.iter()
+        .copied()
+        .filter(|&m| beats(ruleset, m, target))
+        .min_by_key(|&m| all.iter().filter(|&&x| beats(ruleset, x, m)).count())
+        .unwrap_or(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn best_counter_extended_is_fixed() {
+        let cases = [
+            (Move::Rock, Move::Paper),
+            (Move::Paper, Move::Scissors),
+            (Move::Scissors, Move::Rock),
+            (Move::Lizard, Move::Rock),
+            (Move::Spock, Move::Paper),
+        ];
+        for (predicted, counter) in cases {
+            assert_eq!(best_counter(&Ruleset::Extended, predicted), counter);
+        }
+    }
+
+    #[test]
+    fn best_counter_classic() {
+        assert_eq!(best_counter(&Ruleset::Classic, Move::Rock), Move::Paper);
+        assert_eq!(best_counter(&Ruleset::Classic, Move::Paper), Move::Scissors);
+        assert_eq!(best_counter(&Ruleset::Classic, Move::Scissors), Move::Rock);
+    }
+
+    #[test]
+    fn best_counter_prefers_hardest_to_punish() {
+        // b and c both beat a, but only b can itself be beaten (by d).
+        let rules = Ruleset::Custom(CustomRuleset {
+            name: "test".to_string(),
+            moves: vec!["a".into(), "b".into(), "c".into(), "d".into()],
+            beats: HashMap::from([
+                ("b".to_string(), vec!["a".to_string()]),
+                ("c".to_string(), vec!["a".to_string()]),
+                ("d".to_string(), vec!["b".to_string()]),
+            ]),
+        });
+        assert_eq!(best_counter(&rules, Move::Custom(0)), Move::Custom(2));
+    }
+
+    #[test]
+    fn generalized_rulesets_are_balanced_and_named_apart() {
+        let rules = Ruleset::Custom(CustomRuleset::generalized(7, None).unwrap());
+        let all = Move::all_for_ruleset(&rules);
+        assert_eq!(all.len(), 7);
+        for &a in &all {
+            assert_eq!(all.iter().filter(|&&b| beats(&rules, a, b)).count(), 3);
+        }
+        assert_eq!(parse_move("m3", &rules), Some(Move::Custom(2)));
+        assert_eq!(parse_move("3", &rules), Some(Move::Custom(2)));
+
+        let named = CustomRuleset::generalized(3, Some(vec!["a".into(), "b".into(), "c".into()]));
+        let names = [
+            rules.name().to_string(),
+            CustomRuleset::generalized(5, None).unwrap().name,
+            CustomRuleset::generalized(3, None).unwrap().name,
+            named.unwrap().name,
+        ];
+        for (i, a) in names.iter().enumerate() {
+            assert!(names[i + 1..].iter().all(|b| a != b), "{} repeats", a);
+        }
+        assert!(CustomRuleset::generalized(4, None).is_err());
+    }
+
+    // Written by the first release, before saves carried a version.
+    const V1_SAVE: &str = r#"{
+        "state": {
+            "config": {
+                "player1": "Alice",
+                "player2": "Computer",
+                "mode": "SinglePlayer",
+                "ruleset": "Classic",
+                "format": { "BestOfN": 3 },
+                "difficulty": "Hard"
+            },
+            "round_number": 2,
+            "p1_round_wins": 1,
+            "p2_round_wins": 0,
+            "history": [
+                { "round": 1, "p1_move": "Rock", "p2_move": "Scissors", "winner": "Player1" }
+            ],
+            "human_recent": ["Rock"],
+            "turn": "WaitingP1"
+        },
+        "scoreboard": {
+            "players": {
+                "Alice": { "matches_played": 4, "matches_won": 3, "rounds_won": 9 }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn v1_save_migrates() {
+        let sd = parse_save(V1_SAVE).expect("v1 save should load");
+        assert_eq!(sd.version, SAVE_VERSION);
+        assert_eq!(sd.state.migrated_from, Some(1));
+        assert_eq!(sd.state.config.player1, "Alice");
+        assert_eq!(sd.state.config.ai_memory, DEFAULT_AI_MEMORY);
+        assert!(sd.state.config.use_color);
+        assert_eq!(sd.state.history[0].p1_move, Move::Rock);
+        assert_eq!(sd.scoreboard.players["Alice"].matches_won, 3);
+        assert_eq!(sd.scoreboard.players["Alice"].elo, DEFAULT_ELO);
+    }
+
+    #[test]
+    fn handicap_counts_toward_the_match() {
+        let config = GameConfig {
+            handicap: (2, 0),
+            ..match_with(MatchFormat::FirstToK(3)).config
+        };
+        let (rock, scissors) = (Move::Rock, Move::Scissors);
+
+        let mut state = MatchState::new(config.clone());
+        assert!(check_match_winner(&state).is_none());
+        apply_round(&mut state, rock, scissors, RoundWinner::Player1);
+        assert!(matches!(
+            check_match_winner(&state),
+            Some(RoundWinner::Player1)
+        ));
+
+        // Bob has to win all three, and a rematch starts from the handicap.
+        state.reset_for_rematch();
+        assert_eq!((state.p1_round_wins, state.p2_round_wins), (2, 0));
+        for _ in 0..2 {
+            apply_round(&mut state, scissors, rock, RoundWinner::Player2);
+            assert!(check_match_winner(&state).is_none());
+        }
+        apply_round(&mut state, scissors, rock, RoundWinner::Player2);
+        assert!(matches!(
+            check_match_winner(&state),
+            Some(RoundWinner::Player2)
+        ));
+
+        // Best of 5 from 2-0: the match still ends after five decisive rounds.
+        let mut state = MatchState::new(GameConfig {
+            format: MatchFormat::BestOfN(5),
+            ..config
+        });
+        apply_round(&mut state, scissors, rock, RoundWinner::Player2);
+        apply_round(&mut state, scissors, rock, RoundWinner::Player2);
+        assert!(check_match_winner(&state).is_none());
+        apply_round(&mut state, rock, scissors, RoundWinner::Player1);
+        assert!(matches!(
+            check_match_winner(&state),
+            Some(RoundWinner::Player1)
+        ));
+
+        assert!(valid_handicap(&MatchFormat::BestOfN(5), (2, 0)));
+        assert!(!valid_handicap(&MatchFormat::BestOfN(5), (3, 0)));
+        assert!(!valid_handicap(&MatchFormat::BestOfN(4), (2, 2)));
+        assert!(!valid_handicap(&MatchFormat::FirstToK(3), (0, 3)));
+        assert!(!valid_handicap(&MatchFormat::SingleRound, (1, 0)));
+    }
+
+    #[test]
+    fn a_replayed_tie_doesnt_hide_the_move_that_lost() {
+        use Move::{Paper as P, Rock as R, Scissors as S};
+        let mut state = match_with(MatchFormat::FirstToK(5));
+        state.config.mode = Mode::SinglePlayer;
+        state.config.difficulty = Some(Difficulty::Hard);
+        state.human_recent = vec![R; 5];
+        apply_round(&mut state, S, P, RoundWinner::Player1);
+        // The tie that was replayed: the AI's move is remembered, the round isn't.
+        state.ai_recent.push(R);
+
+        // Hard expects Rock, but Paper just lost.
+        let decision = ai_move(&mut state, R);
+        assert_eq!(decision.predicted, Some(R));
+        assert_ne!(decision.chosen, P);
+    }
+
+    #[test]
+    fn mirror_counters_the_previous_move() {
+        for seed in 0..20 {
+            let mut state = MatchState::new(GameConfig {
+                player2: "Computer".to_string(),
+                mode: Mode::SinglePlayer,
+                format: MatchFormat::FirstToK(5),
+                difficulty: Some(Difficulty::Mirror),
+                rng_seed: Some(seed),
+                ..match_with(MatchFormat::SingleRound).config
+            });
+            let first = ai_move(&mut state, Move::Rock).chosen;
+            let winner = decide_winner(&Ruleset::Classic, Move::Rock, first);
+            apply_round(&mut state, Move::Rock, first, winner);
+            assert_eq!(ai_move(&mut state, Move::Scissors).chosen, Move::Paper);
+        }
+    }
+
+    // A human who keeps tying their own counts, so most_common has ties to
+    // break every few rounds.
+    fn seeded_ai_moves(difficulty: Difficulty, seed: u64) -> Vec<Move> {
+        use Move::{Paper as P, Rock as R, Scissors as S};
+        let mut state = MatchState::new(GameConfig {
+            player2: "Computer".to_string(),
+            mode: Mode::SinglePlayer,
+            format: MatchFormat::FirstToK(100),
+            difficulty: Some(difficulty),
+            rng_seed: Some(seed),
+            ..match_with(MatchFormat::SingleRound).config
+        });
+        let human = [R, P, P, R, S, S, R, P, S, P, R, S, R, P, P, S];
+        human
+            .iter()
+            .map(|&h| {
+                let ai = ai_move(&mut state, h).chosen;
+                let winner = decide_winner(&Ruleset::Classic, h, ai);
+                apply_round(&mut state, h, ai, winner);
+                ai
+            })
+            .collect()
+    }
+
+    #[test]
+    fn seeded_hard_and_expert_play_the_same_game() {
+        use Move::{Paper as P, Rock as R, Scissors as S};
+        assert_eq!(
+            seeded_ai_moves(Difficulty::Hard, 7),
+            [P, P, S, S, P, R, R, P, S, R, S, P, R, P, P, S]
+        );
+        assert_eq!(
+            seeded_ai_moves(Difficulty::Expert, 7),
+            [P, P, S, S, P, R, R, P, S, R, P, S, S, R, P, S]
+        );
+    }
+
+    #[test]
+    fn everyone_faces_the_same_daily_challenge() {
+        let seed = challenge_seed("2026-10-14");
+        assert_eq!(seed, challenge_seed("2026-10-14"));
+        assert_ne!(seed, challenge_seed("2026-10-15"));
+        assert_eq!(
+            seeded_ai_moves(Difficulty::Hard, seed),
+            seeded_ai_moves(Difficulty::Hard, seed)
+        );
+    }
+
+    #[test]
+    fn ascii_art_is_ascii() {
+        for mv in Move::all_for_ruleset(&Ruleset::Extended) {
+            assert!(ascii_move(mv).is_ascii(), "{:?}", mv);
+        }
+    }
+
+    #[test]
+    fn non_ascii_art_falls_back_to_the_built_in() {
+        let dir = std::env::temp_dir().join(format!("rps-art-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("rock.txt"), "  (●)\n").unwrap();
+        fs::write(dir.join("paper.txt"), "  [ ]\n").unwrap();
+
+        let problems = load_art(&dir);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].0.ends_with("rock.txt"));
+        assert!(matches!(problems[0].1, ArtError::NotAscii));
+        assert_eq!(ascii_move(Move::Rock), ROCK_ART);
+        assert_eq!(ascii_move(Move::Paper), "\n  [ ]\n");
+
+        // Put the built-in art back for the other tests.
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(load_art(&dir).is_empty());
+        assert_eq!(ascii_move(Move::Paper), PAPER_ART);
+    }
+
+    #[test]
+    fn sort_rows_breaks_ties() {
+        let row = |name: &str, played, won, rounds| {
+            let stats = PlayerStats {
+                matches_played: played,
+                matches_won: won,
+                rounds_won: rounds,
+                ..PlayerStats::default()
+            };
+            let rate = win_rate(&stats);
+            (name.to_string(), stats, rate)
+        };
+        let mut rows = vec![
+            row("Cat", 2, 1, 3),
+            row("Bob", 4, 2, 5),
+            row("Dan", 0, 0, 0),
+            row("Amy", 2, 1, 3),
+            row("Eve", 4, 2, 7),
+        ];
+        rows.push(("Nan".to_string(), PlayerStats::default(), f32::NAN));
+
+        sort_rows(&mut rows, SortKey::WinRate);
+        let names: Vec<&str> = rows.iter().map(|r| r.0.as_str()).collect();
+        assert_eq!(names, ["Eve", "Bob", "Amy", "Cat", "Dan", "Nan"]);
+
+        sort_rows(&mut rows, SortKey::MatchesWon);
+        let names: Vec<&str> = rows.iter().map(|r| r.0.as_str()).collect();
+        assert_eq!(names, ["Eve", "Bob", "Amy", "Cat", "Dan", "Nan"]);
+    }
+
+    #[test]
+    fn win_probability_matches_hand_counts() {
+        let classic = random_round_odds(&Ruleset::Classic);
+        let close = |(a, b): (f64, f64), (x, y): (f64, f64)| {
+            assert!((a - x).abs() < 1e-9 && (b - y).abs() < 1e-9, "{} {}", a, b);
+        };
+        close(
+            win_probability(MatchFormat::BestOfN(3), (0, 0), classic),
+            (0.5, 0.5),
+        );
+        // Needs one of the next two decisive rounds: 1 - 1/4.
+        close(
+            win_probability(MatchFormat::BestOfN(3), (1, 0), classic),
+            (0.75, 0.25),
+        );
+        // Needs two of the next three.
+        close(
+            win_probability(MatchFormat::FirstToK(3), (1, 0), classic),
+            (0.6875, 0.3125),
+        );
+        // 1-1 in a best of 2 can only be a draw.
+        close(
+            win_probability(MatchFormat::BestOfN(2), (1, 1), classic),
+            (0.0, 0.0),
+        );
+        // One more round decides a points match at 2-2 with target 3: a
+        // tie takes both to 3 and draws.
+        let (p1, p2) = win_probability(MatchFormat::Points { target: 3 }, (2, 2), classic);
+        close((p1, p2), (1.0 / 3.0, 1.0 / 3.0));
+    }
+
+    #[test]
+    fn detect_cycle_finds_clean_cycles() {
+        use Move::{Paper as P, Rock as R, Scissors as S};
+        assert_eq!(
+            detect_cycle(&[R, P, S, R, P, S, R, P, S]),
+            Some(vec![R, P, S])
+        );
+        assert_eq!(
+            detect_cycle(&[S, R, P, S, R, P, S, R, P, S]),
+            Some(vec![R, P, S])
+        );
+        assert_eq!(detect_cycle(&[R, P, R, P, R, P]), Some(vec![R, P]));
+        assert_eq!(
+            detect_cycle(&[R, R, P, S, R, R, P, S, R, R, P, S]),
+            Some(vec![R, R, P, S])
+        );
+    }
+
+    #[test]
+    fn detect_cycle_needs_confidence() {
+        use Move::{Paper as P, Rock as R, Scissors as S};
+        // Too few repeats.
+        assert_eq!(detect_cycle(&[R, P, S, R, P, S]), None);
+        // A slip inside the window.
+        assert_eq!(detect_cycle(&[R, P, S, R, S, S, R, P, S]), None);
+        assert_eq!(detect_cycle(&[R, S, P, P, R, S, S, P, R, R, P, S]), None);
+        // The same move over and over isn't a cycle.
+        assert_eq!(detect_cycle(&[R, R, R, R, R, R, R, R, R]), None);
+        assert_eq!(detect_cycle(&[]), None);
+    }
+
+    #[test]
+    fn weighted_prediction_follows_a_switch_sooner() {
+        use Move::{Paper as P, Rock as R};
+        // Rounds of Paper needed after a run of Rock before the guess moves.
+        let flips_after = |predict: &dyn Fn(&[Move]) -> Option<Move>| {
+            let mut recent = vec![R; 9];
+            (1..=12)
+                .find(|_| {
+                    recent.remove(0);
+                    recent.push(P);
+                    predict(&recent) == Some(P)
+                })
+                .unwrap()
+        };
+        let weighted = flips_after(&|r| weighted_prediction(r, 0.8));
+        let plain = flips_after(&|r| most_common(r));
+        assert_eq!(weighted, 3);
+        assert!(weighted < plain, "{} vs {}", weighted, plain);
+
+        assert_eq!(weighted_prediction(&[R, R, P], 0.8), Some(R));
+        // No decay is a plain count, with the tie going to the latest move.
+        assert_eq!(weighted_prediction(&[R, P], 1.0), Some(P));
+        assert_eq!(weighted_prediction(&[], 0.8), None);
+    }
+
+    #[test]
+    fn match_move_fixes_typos() {
+        let none = MoveAliases::new();
+        let classic = Ruleset::Classic;
+        assert_eq!(
+            match_move("rck", &classic, &none),
+            MoveMatch::Found(Move::Rock)
+        );
+        assert_eq!(
+            match_move("sissors", &classic, &none),
+            MoveMatch::Found(Move::Scissors)
+        );
+        assert_eq!(
+            match_move("Papper", &classic, &none),
+            MoveMatch::Found(Move::Paper)
+        );
+        assert_eq!(match_move("rcok", &classic, &none), MoveMatch::NotFound);
+        assert_eq!(match_move("x", &classic, &none), MoveMatch::NotFound);
+    }
+
+    #[test]
+    fn match_move_reports_ambiguity() {
+        let none = MoveAliases::new();
+        // One edit from both "rock" and "spock".
+        assert_eq!(
+            match_move("sock", &Ruleset::Extended, &none),
+            MoveMatch::Ambiguous(vec![Move::Rock, Move::Spock])
+        );
+        // Without Spock in the ruleset there's only one candidate.
+        assert_eq!(
+            match_move("sock", &Ruleset::Classic, &none),
+            MoveMatch::Found(Move::Rock)
+        );
+        // Single letters stay on the strict mapping: "s" is Scissors, "k" Spock.
+        assert_eq!(
+            match_move("s", &Ruleset::Extended, &none),
+            MoveMatch::Found(Move::Scissors)
+        );
+        assert_eq!(
+            match_move("k", &Ruleset::Extended, &none),
+            MoveMatch::Found(Move::Spock)
+        );
+    }
+
+    #[test]
+    fn match_move_uses_aliases() {
+        let aliases: MoveAliases = [("piedra", "rock"), ("lagarto", "lizard")]
+            .into_iter()
+            .map(|(a, n)| (a.to_string(), n.to_string()))
+            .collect();
+        assert_eq!(
+            match_move("Piedra", &Ruleset::Classic, &aliases),
+            MoveMatch::Found(Move::Rock)
+        );
+        assert_eq!(
+            match_move("piedrs", &Ruleset::Classic, &aliases),
+            MoveMatch::Found(Move::Rock)
+        );
+        // An alias for a move outside the ruleset doesn't apply.
+        assert_eq!(
+            match_move("lagarto", &Ruleset::Classic, &aliases),
+            MoveMatch::NotFound
+        );
+    }
+
+    #[test]
+    fn newer_save_is_rejected() {
+        let json = format!(r#"{{"version": {}}}"#, SAVE_VERSION + 1);
+        assert!(parse_save(&json).is_err());
+    }
+
+    #[test]
+    fn canonical_names_ignore_case_and_spacing() {
+        assert_eq!(canonical_name("  Alice "), "alice");
+        assert_eq!(canonical_name("Mary  Ann"), canonical_name("mary ann"));
+        assert_ne!(canonical_name("Alice"), canonical_name("Alicia"));
+    }
+
+    #[test]
+    fn names_differing_by_case_share_a_scoreboard_entry() {
+        let mut sb = Scoreboard::default();
+        sb.record_move("Alice", Move::Rock);
+        sb.record_move("alice ", Move::Paper);
+        assert_eq!(sb.players.len(), 1);
+        assert!(sb.players.contains_key("Alice"));
+        assert_eq!(sb.player(" ALICE").map(|s| s.move_counts.len()), Some(2));
+
+        assert!(sb.remove_player("ALICE"));
+        assert!(sb.players.is_empty());
+    }
+
+    #[test]
+    fn profile_keeps_only_the_players_records() {
+        let mut sb = Scoreboard::default();
+        for (p1, p2) in [("Alice", "Bob"), ("Bob", "Carol"), ("Carol", "Alice")] {
+            let mut state = match_with(MatchFormat::SingleRound);
+            state.config.player1 = p1.to_string();
+            state.config.player2 = p2.to_string();
+            apply_round(&mut state, Move::Rock, Move::Scissors, RoundWinner::Player1);
+            sb.add_match_result(&state, RoundWinner::Player1);
+            sb.record_challenge("2026-01-01", &state, RoundWinner::Player1);
+        }
+
+        let profile = sb.profile("alice").unwrap();
+        assert_eq!(profile.players.keys().collect::<Vec<_>>(), ["Alice"]);
+        let pairs: usize = profile.head_to_head.values().map(|m| m.len()).sum();
+        assert_eq!(pairs, 2);
+        assert_eq!(profile.head_to_head("Alice", "Bob"), (1, 0, 0));
+        assert_eq!(profile.head_to_head("Alice", "Carol"), (0, 1, 0));
+        assert_eq!(profile.head_to_head("Bob", "Carol"), (0, 0, 0));
+        let results = &profile.challenges["2026-01-01"];
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].player, "Alice");
+        assert!(sb.profile("Dave").is_none());
+    }
+
+    #[test]
+    fn response_times_average_and_merge() {
+        let mut sb = Scoreboard::default();
+        assert_eq!(sb.player("Alice").and_then(|s| s.average_response()), None);
+        sb.record_response("Alice", Duration::from_millis(1500));
+        sb.record_response("alice", Duration::from_millis(500));
+        let st = sb.player("Alice").unwrap();
+        assert_eq!(st.average_response(), Some(Duration::from_millis(1000)));
+        assert_eq!(st.fastest_response_ms, Some(500));
+
+        // The faster of the two fastest survives a merge, and the average
+        // covers every response on both sides.
+        let mut other = Scoreboard::default();
+        other.record_response("Alice", Duration::from_millis(200));
+        other.record_response("Bob", Duration::from_millis(900));
+        sb.merge(other);
+        let st = sb.player("Alice").unwrap();
+        assert_eq!(st.responses, 3);
+        assert_eq!(st.fastest_response_ms, Some(200));
+        assert_eq!(st.average_response(), Some(Duration::from_millis(733)));
+        assert_eq!(sb.player("Bob").unwrap().fastest_response_ms, Some(900));
+    }
+
+    #[test]
+    fn closed_input_is_not_a_blank_line() {
+        let mut closed: &[u8] = b"";
+        assert_eq!(read_input_line(&mut closed), None);
+
+        let mut piped: &[u8] = b"rock\n\n";
+        assert_eq!(read_input_line(&mut piped).as_deref(), Some("rock\n"));
+        assert_eq!(read_input_line(&mut piped).as_deref(), Some("\n"));
+        assert_eq!(read_input_line(&mut piped), None);
+    }
+
+    fn match_with(format: MatchFormat) -> MatchState {
+        MatchState::new(GameConfig {
+            player1: "Alice".to_string(),
+            player2: "Bob".to_string(),
+            mode: Mode::Multiplayer,
+            format,
+            rng_seed: Some(1),
+            use_color: false,
+            show_ascii: false,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn best_of_one_plays_through_ties_unlike_a_single_round() {
+        let (rock, scissors) = (Move::Rock, Move::Scissors);
+
+        let mut state = match_with(MatchFormat::BestOfN(1));
+        apply_round(&mut state, rock, rock, RoundWinner::Tie);
+        assert_eq!(check_match_winner(&state), None);
+        apply_round(&mut state, scissors, rock, RoundWinner::Player2);
+        assert_eq!(check_match_winner(&state), Some(RoundWinner::Player2));
+
+        let mut state = match_with(MatchFormat::SingleRound);
+        assert_eq!(check_match_winner(&state), None);
+        apply_round(&mut state, rock, rock, RoundWinner::Tie);
+        assert_eq!(check_match_winner(&state), Some(RoundWinner::Tie));
+    }
+
+    #[test]
+    fn first_to_one_ends_on_the_first_win() {
+        let mut state = match_with(MatchFormat::FirstToK(1));
+        apply_round(&mut state, Move::Rock, Move::Rock, RoundWinner::Tie);
+        assert_eq!(check_match_winner(&state), None);
+        apply_round(&mut state, Move::Rock, Move::Scissors, RoundWinner::Player1);
+        assert_eq!(check_match_winner(&state), Some(RoundWinner::Player1));
+    }
+
+    #[test]
+    fn mid_match_best_of_is_undecided_until_a_majority() {
+        let (rock, scissors) = (Move::Rock, Move::Scissors);
+        let mut state = match_with(MatchFormat::BestOfN(5));
+        for winner in [
+            RoundWinner::Player1,
+            RoundWinner::Tie,
+            RoundWinner::Player2,
+            RoundWinner::Player1,
+        ] {
+            apply_round(&mut state, rock, scissors, winner);
+            assert_eq!(check_match_winner(&state), None);
+        }
+        apply_round(&mut state, rock, scissors, RoundWinner::Player1);
+        assert_eq!(check_match_winner(&state), Some(RoundWinner::Player1));
+
+        // Even N can run out of rounds level.
+        let mut state = match_with(MatchFormat::BestOfN(2));
+        apply_round(&mut state, rock, scissors, RoundWinner::Player1);
+        apply_round(&mut state, scissors, rock, RoundWinner::Player2);
+        assert_eq!(check_match_winner(&state), Some(RoundWinner::Tie));
+    }
+
+    #[test]
+    fn earlier_player_over_the_line_wins() {
+        let (rock, scissors) = (Move::Rock, Move::Scissors);
+        let mut state = match_with(MatchFormat::FirstToK(2));
+        apply_round(&mut state, scissors, rock, RoundWinner::Player2);
+        apply_round(&mut state, scissors, rock, RoundWinner::Player2);
+        apply_round(&mut state, rock, scissors, RoundWinner::Player1);
+        apply_round(&mut state, rock, scissors, RoundWinner::Player1);
+        assert_eq!(check_match_winner(&state), Some(RoundWinner::Player2));
+    }
+
+    #[test]
+    fn adaptive_guesses_from_earlier_moves() {
+        let mut state = match_with(MatchFormat::FirstToK(3));
+        state.config.mode = Mode::SinglePlayer;
+        state.config.difficulty = Some(Difficulty::Adaptive);
+        assert_eq!(ai_move(&mut state, Move::Rock).predicted, None);
+        assert!(state.ai_predictions.is_empty());
+        for _ in 0..3 {
+            ai_move(&mut state, Move::Rock);
+        }
+        assert_eq!(state.ai_predictions.len(), 3);
+    }
+
+    #[test]
+    fn normal_counters_are_not_predictions() {
+        let mut state = match_with(MatchFormat::FirstToK(3));
+        state.config.mode = Mode::SinglePlayer;
+        state.config.difficulty = Some(Difficulty::Normal);
+        state.config.counter_chance = 100;
+        for _ in 0..5 {
+            let decision = ai_move(&mut state, Move::Rock);
+            assert_eq!(decision.predicted, Some(Move::Rock));
+        }
+        assert_eq!(prediction_accuracy(&state), None);
+
+        // Hard guesses from earlier moves only, and those do count.
+        state.config.difficulty = Some(Difficulty::Hard);
+        ai_move(&mut state, Move::Paper);
+        assert_eq!(prediction_accuracy(&state), Some((0, 1)));
+    }
+
+    #[test]
+    fn win_by_two_plays_on_through_deuce() {
+        let (rock, scissors) = (Move::Rock, Move::Scissors);
+        let mut state = match_with(MatchFormat::FirstToKWinBy2(3));
+        for winner in [
+            RoundWinner::Player1,
+            RoundWinner::Player1,
+            RoundWinner::Player2,
+            RoundWinner::Player2,
+            RoundWinner::Player1,
+        ] {
+            apply_round(&mut state, rock, scissors, winner);
+            assert_eq!(check_match_winner(&state), None);
+        }
+        // 3-2 is match point but not the match.
+        assert_eq!(match_point(&state), (true, false));
+        assert_eq!(wins_to_go(&state), Some((1, 3)));
+
+        apply_round(&mut state, scissors, rock, RoundWinner::Player2);
+        assert_eq!(check_match_winner(&state), None);
+        assert_eq!(wins_to_go(&state), Some((2, 2)));
+        apply_round(&mut state, scissors, rock, RoundWinner::Player2);
+        apply_round(&mut state, rock, scissors, RoundWinner::Player1);
+        apply_round(&mut state, rock, scissors, RoundWinner::Player1);
+        assert_eq!(check_match_winner(&state), None);
+        apply_round(&mut state, rock, scissors, RoundWinner::Player1);
+        assert_eq!(check_match_winner(&state), Some(RoundWinner::Player1));
+        // Nothing was safe until the last round.
+        let last = state.history.last().unwrap();
+        assert!(decisive_round(&state).is_some_and(|r| std::ptr::eq(r, last)));
+    }
+
+    #[test]
+    fn round_limit_ends_a_long_deuce() {
+        let (rock, scissors) = (Move::Rock, Move::Scissors);
+        let mut state = match_with(MatchFormat::FirstToKWinBy2(2));
+        state.config.max_rounds = Some(5);
+        for winner in [
+            RoundWinner::Player1,
+            RoundWinner::Player2,
+            RoundWinner::Player1,
+            RoundWinner::Player2,
+        ] {
+            apply_round(&mut state, rock, scissors, winner);
+        }
+        assert_eq!(check_match_winner(&state), None);
+        apply_round(&mut state, rock, scissors, RoundWinner::Player1);
+        assert_eq!(check_match_winner(&state), Some(RoundWinner::Player1));
+        assert!(ended_by_round_limit(&state));
+
+        // Deuce is even odds from level, whatever K was.
+        let classic = random_round_odds(&Ruleset::Classic);
+        let (p1, p2) = win_probability(MatchFormat::FirstToKWinBy2(3), (4, 4), classic);
+        assert!((p1 - 0.5).abs() < 1e-9 && (p2 - 0.5).abs() < 1e-9);
+        let (p1, _) = win_probability(MatchFormat::FirstToKWinBy2(3), (3, 2), classic);
+        assert!((p1 - 0.75).abs() < 1e-9);
+    }
+}
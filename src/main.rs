@@ -1,12 +1,72 @@
+// Everything the game prints goes through output, so a test can capture it.
+macro_rules! print {
+    ($($arg:tt)*) => {
+        $crate::output::print(format_args!($($arg)*))
+    };
+}
+
+macro_rules! println {
+    () => {
+        $crate::output::print(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::print(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+mod challenge;
+mod cli;
+mod color;
+mod editor;
+mod input;
+mod interrupt;
+mod lang;
+mod layout;
+mod network;
+mod output;
+mod replay;
+mod series;
+mod settings;
+mod sim;
+mod sound;
+mod tournament;
+
+use color::{accent, lose, notice, tie, win};
+use lang::{move_name, t, tf};
 use rand::Rng;
 use rpassword::read_password;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use rps_game::{
+    ai_move, apply_round, apply_tiebreaker, ascii_move, beats, beats_table, canonical_name,
+    check_match_winner, check_ruleset, clear_saved_game, commitment, computer_name, decide_winner,
+    decisive_round, displayed_score, ended_by_round_limit, list_ruleset_files, list_save_slots,
+    load_aliases, load_art, load_saved_game, log_round, match_move, match_point,
+    migrate_legacy_save, move_timeout, new_nonce, observed_round_odds, prediction_accuracy,
+    random_from, random_round_odds, rounds_needed, save_conflict, save_game, seed_from_env,
+    sort_rows, sudden_death, tie_is_replayed, unix_now, valid_handicap, verify_commitment,
+    win_probability, win_rate, wins_to_go, AiDecision, ArtError, CustomRuleset, Defaults,
+    Difficulty, GameConfig, MatchFormat, MatchState, Mode, Move, MoveAliases, MoveMatch, Persona,
+    PlayerStats, Rationale, RoundRecord, RoundWinner, Ruleset, ScoreRow, Scoreboard, SortKey,
+    TournamentState, Turn, ART_DIR, DEFAULT_AI_MEMORY, DEFAULT_COUNTER_CHANCE, MAX_AI_MEMORY,
+    RULESET_DIR, SAVE_FILE, SCORE_FILE,
+};
+use std::fmt::Display;
 use std::fs;
-use std::io::{self, Write};
-
-const SAVE_FILE: &str = "rps_save.json";
-const SCORE_FILE: &str = "rps_scoreboard.json";
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::sync::{Mutex, Once, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const ANIMATION_ENV: &str = "RPS_ANIMATION_MS";
+const DEFAULT_ANIMATION_MS: u64 = 400;
+const HISTOGRAM_WIDTH: usize = 20;
+const NAME_WIDTH: usize = 20;
+const HEADING_WIDTH: usize = 30;
+// Everything right of the name column in the scoreboard and player details.
+const SCOREBOARD_STATS_WIDTH: usize = 66;
+const DETAILS_STATS_WIDTH: usize = 34;
+const STANDINGS_FILE: &str = "rps_standings.md";
+const SPARKLINE_ROUNDS: usize = 40;
 
 enum AfterMatchAction {
     ContinueMatch,
@@ -17,59 +77,159 @@ enum AfterRoundAction {
     NextRound,
     SaveAndMenu,
     MenuNoSave,
+    // The match goes to this side.
+    Concede(RoundWinner),
 }
 
 fn main() {
-    let mut scoreboard = Scoreboard::load();
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|a| a == "sim") {
+        if let Err(e) = sim::run(&args[1..]) {
+            eprintln!("rps sim: {}\n\n{}", e, sim::USAGE);
+            std::process::exit(2);
+        }
+        return;
+    }
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{}", cli::USAGE);
+        return;
+    }
+    match cli::take_script(&mut args) {
+        Ok(None) => {}
+        Ok(Some(path)) => {
+            if let Err(e) = input::load_script(&path) {
+                eprintln!("rps: can't read {}: {}", path, e);
+                std::process::exit(2);
+            }
+        }
+        Err(e) => {
+            eprintln!("rps: {}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    }
+    let launch = match cli::parse_args(&args) {
+        Ok(launch) => launch,
+        Err(e) => {
+            eprintln!("rps: {}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    color::set_enabled(true);
+    interrupt::install();
+    let mut scoreboard = Scoreboard::load().unwrap_or_else(|backup| {
+        let warning = match backup {
+            Some(path) => tf("warn.scoreboard_moved", &[&SCORE_FILE, &path]),
+            None => tf("warn.scoreboard_unreadable", &[&SCORE_FILE]),
+        };
+        println!("{}", notice(&warning));
+        pause();
+        Scoreboard::default()
+    });
+    let mut defaults = Defaults::load();
+    set_animation_delay(defaults.animation_ms);
+    input::set_single_key(defaults.single_key == Some(true));
+    sound::set_enabled(defaults.sound == Some(true));
+    load_move_art(&defaults);
+    load_theme(&defaults);
+
+    let state = match launch {
+        cli::Launch::Menu => None,
+        cli::Launch::Resume(slot) => Some(resume_slot(slot)),
+        cli::Launch::Match(config) => Some(Ok(MatchState::new(config))),
+        cli::Launch::Host(config, addr) => Some(network::host(&addr, MatchState::new(config))),
+        cli::Launch::Connect(config, addr) => Some(network::connect(&addr, &config)),
+    };
+    if let Some(state) = state {
+        match state {
+            Ok(mut state) => run_match(&mut state, &mut scoreboard),
+            Err(e) => {
+                eprintln!("rps: {}", e);
+                std::process::exit(1);
+            }
+        }
+        scoreboard.save();
+        return;
+    }
 
     welcome_screen();
 
     loop {
         clear_screen();
 
-        println!("==============================");
-        println!("           Main Menu");
-        println!("==============================\n");
-
-        println!("Choose an option (1-5):\n");
-
-        println!("1) Start a new game");
-        println!("2) Continue saved game");
-        println!("3) View scoreboard");
-        println!("4) Reset match history + scoreboard");
-        println!("5) Exit");
+        print_heading(t("main.title"));
+
+        let items = [
+            "main.new",
+            "main.quick",
+            "main.challenge",
+            "main.continue",
+            "main.tournament",
+            "main.continue_tournament",
+            "main.series",
+            "main.replay",
+            "main.editor",
+            "main.scoreboard",
+            "main.reset",
+            "main.settings",
+            "main.exit",
+        ];
+        println!("{}\n", tf("main.choose", &[&items.len()]));
+        for (i, key) in items.iter().enumerate() {
+            menu_item(i + 1, t(key));
+        }
 
-        match read_menu_choice(1, 5) {
+        match read_menu_choice(1, items.len() as i32) {
             1 => {
-                let config = new_game_setup();
+                let config = new_game_setup(&defaults);
+                defaults.remember(&config);
+                defaults.save();
                 let mut state = MatchState::new(config);
                 run_match(&mut state, &mut scoreboard);
             }
-            2 => match load_saved_game() {
-                Ok((mut state, saved_board)) => {
-                    scoreboard = saved_board;
-                    scoreboard.save();
-                    run_match(&mut state, &mut scoreboard);
+            2 => {
+                let mut state = MatchState::new(quick_play_config(&defaults));
+                run_match(&mut state, &mut scoreboard);
+            }
+            3 => challenge::daily_challenge(&defaults, &mut scoreboard),
+            4 => {
+                if let Some(state) = choose_saved_game() {
+                    if let Some(mut state) = resume_network(state) {
+                        run_match(&mut state, &mut scoreboard);
+                    }
                 }
-                Err(_) => {
-                    println!("\nNo saved game found.");
-                    pause();
+            }
+            5 => tournament::run_tournament(&mut scoreboard),
+            6 => tournament::continue_tournament(&mut scoreboard),
+            7 => {
+                let config = new_game_setup(&defaults);
+                defaults.remember(&config);
+                defaults.save();
+                series::run_series(config, &mut scoreboard);
+            }
+            8 => {
+                if let Some(state) = replay::choose_replay() {
+                    color::set_enabled(should_use_color(&state.config));
+                    replay::replay_match(&state);
+                    color::set_enabled(true);
                 }
-            },
-            3 => view_scoreboard(&scoreboard),
-            4 => {
+            }
+            9 => editor::ruleset_editor(),
+            10 => view_scoreboard(&mut scoreboard, defaults.ranking_threshold()),
+            11 => {
                 reset_all_data();
                 scoreboard = Scoreboard::default();
-    scoreboard.save();
+                scoreboard.save();
 
                 clear_screen();
-                println!("All data reset (save + scoreboard).");
+                println!("{}", t("main.reset_done"));
 
                 pause();
             }
-            5 => {
+            12 => settings::settings_menu(&mut defaults),
+            13 => {
                 scoreboard.save();
-                println!("\nGoodbye.");
+                println!("\n{}", t("main.goodbye"));
                 break;
             }
             _ => {}
@@ -81,45 +241,69 @@ fn welcome_screen() {
     clear_screen();
     banner();
 
-    println!("Welcome to the Rock, Paper, Scissors game.");
+    println!("{}", t("banner.welcome"));
     println!();
-    println!("Press Enter to continue.");
+    println!("{}", t("common.enter_continue"));
 
-    let mut s = String::new();
-    let _ = io::stdin().read_line(&mut s);
+    let _ = input::line();
 }
 
 fn reset_all_data() {
     let _ = std::fs::remove_file(SAVE_FILE);
+    for slot in list_save_slots() {
+        clear_saved_game(Some(slot));
+    }
     let _ = std::fs::remove_file(SCORE_FILE);
+    TournamentState::clear();
 }
 
 fn banner() {
-    let title = "Rock, Paper, Scissors";
+    let title = t("banner.title");
 
-    let line1 = "Welcome to the Rock, Paper, Scissors game.";
-    let line2 = "Press Enter to continue to the main menu.";
+    let line1 = t("banner.welcome");
+    let line2 = t("banner.enter");
 
-    let width = line1.len().max(line2.len());
-    let padding = (width - title.len()) / 2;
+    let width = line1.chars().count().max(line2.chars().count());
+    let rule = layout::rule('=', width);
+    let padding = rule.len().saturating_sub(title.chars().count()) / 2;
 
-    println!("{}", "=".repeat(width));
-    println!("{}{}", " ".repeat(padding), title);
-    println!("{}", "=".repeat(width));
+    println!("{}", accent(&rule));
+    println!("{}{}", " ".repeat(padding), color::title(title));
+    println!("{}", accent(&rule));
     println!();
 }
 
+fn print_heading(title: &str) {
+    let rule = layout::rule('=', HEADING_WIDTH);
+    println!("{}", rule);
+    println!(
+        "{}",
+        format!("{:^width$}", title, width = rule.len()).trim_end()
+    );
+    println!("{}\n", rule);
+}
+
+// The escape codes would only garble a file or a dumb terminal.
 fn clear_screen() {
+    if !clearing_enabled() {
+        println!("\n{}", layout::rule('-', 40));
+        return;
+    }
     print!("\x1B[2J\x1B[1;1H");
     let _ = io::stdout().flush();
     println!();
 }
 
+fn clearing_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED
+        .get_or_init(|| io::stdout().is_terminal() && !std::env::args().any(|a| a == "--no-clear"))
+}
+
 fn pause() {
     loop {
-        println!("\nPress Enter to go back.");
-        let mut s = String::new();
-        let _ = io::stdin().read_line(&mut s);
+        println!("\n{}", t("common.enter_back"));
+        let s = input::line().unwrap_or_default();
 
         if s.trim().is_empty() {
             break;
@@ -130,378 +314,586 @@ fn pause() {
 fn read_line(prompt: &str) -> String {
     print!("{}", prompt);
     let _ = io::stdout().flush();
-    let mut input = String::new();
-    if io::stdin().read_line(&mut input).is_err() {
-        return String::new();
+    match input::line() {
+        Some(s) => s.trim().to_string(),
+        None => interrupt::input_closed(),
     }
-    input.trim().to_string()
+}
+
+// The number is in the theme's accent color.
+fn menu_item(n: impl Display, text: impl Display) {
+    println!("{} {}", accent(&format!("{})", n)), text);
 }
 
 fn read_menu_choice(min: i32, max: i32) -> i32 {
+    read_menu_choice_or(min, max, false).unwrap_or(min)
+}
+
+// With a default on offer a blank answer picks it, returned as None.
+fn read_menu_choice_or(min: i32, max: i32, has_default: bool) -> Option<i32> {
     loop {
-        let s = read_line("\nChoose: ");
+        let s = read_line(&format!("\n{}", t("common.choose")));
+        if s.is_empty() && has_default {
+            return None;
+        }
         if let Ok(n) = s.parse::<i32>() {
             if n >= min && n <= max {
-                return n;
+                return Some(n);
             }
         }
-        println!("Invalid choice. Try again.");
+        println!("{}", t("common.invalid_choice"));
     }
 }
 
-fn colorize(s: &str, code: &str) -> String {
-    format!("\x1b[{}m{}\x1b[0m", code, s)
+fn read_yes_no(prompt: &str) -> bool {
+    read_yes_no_or(prompt, None)
 }
 
-fn green(s: &str) -> String {
-    colorize(s, "32")
-}
-fn red(s: &str) -> String {
-    colorize(s, "31")
-}
-fn yellow(s: &str) -> String {
-    colorize(s, "33")
-}
-fn cyan(s: &str) -> String {
-    colorize(s, "36")
+fn read_yes_no_or(prompt: &str, default: Option<bool>) -> bool {
+    if let Some(d) = default {
+        print_default(if d { "y" } else { "n" });
+    }
+    loop {
+        match read_line(prompt).to_lowercase().as_str() {
+            "" if default.is_some() => return default.unwrap_or_default(),
+            "y" | "yes" => return true,
+            "n" | "no" => return false,
+            _ => println!("{}", t("common.yes_no")),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-enum Mode {
-    SinglePlayer,
-    Multiplayer,
+fn load_theme(defaults: &Defaults) {
+    if let Err(name) = color::apply_theme(defaults.theme.as_deref()) {
+        let names = color::theme_names().join(", ");
+        println!("{}", notice(&tf("warn.theme_unknown", &[&name, &names])));
+        pause();
+    }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-enum Ruleset {
-    Classic,
-    Extended,
+fn print_default(value: &str) {
+    println!("{}", tf("setup.default", &[&value]));
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-enum Difficulty {
-    Easy,
-    Normal,
-    Hard,
+fn should_use_color(cfg: &GameConfig) -> bool {
+    cfg.use_color
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-enum MatchFormat {
-    SingleRound,
-    BestOfN(u32),
-    FirstToK(u32),
+// In single-player the computer winning is shown as a loss, since it's one
+// for the human, and likewise the other side winning over the network.
+fn result_line(cfg: &GameConfig, winner: RoundWinner, text: &str) -> String {
+    match winner {
+        RoundWinner::Tie => tie(text),
+        RoundWinner::Player2 if cfg.mode == Mode::SinglePlayer => lose(text),
+        RoundWinner::Player1 if cfg.mode == Mode::Network && !network::local_is_p1() => lose(text),
+        RoundWinner::Player2 if cfg.mode == Mode::Network && network::local_is_p1() => lose(text),
+        _ => win(text),
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct GameConfig {
-    player1: String,
-    player2: String,
-    mode: Mode,
-    ruleset: Ruleset,
-    format: MatchFormat,
-    difficulty: Option<Difficulty>,
+fn emoji_move(mv: Move) -> Option<&'static str> {
+    match mv {
+        Move::Rock => Some("\u{270a}"),
+        Move::Paper => Some("\u{270b}"),
+        Move::Scissors => Some("\u{270c}\u{fe0f}"),
+        Move::Lizard => Some("\u{1f98e}"),
+        Move::Spock => Some("\u{1f596}"),
+        Move::Custom(_) => None,
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-enum Move {
-    Rock,
-    Paper,
-    Scissors,
-    Lizard,
-    Spock,
+fn streak_label(streak: i32) -> String {
+    match streak {
+        0 => "-".to_string(),
+        n if n > 0 => tf("streak.win", &[&n]),
+        n => tf("streak.loss", &[&-n]),
+    }
 }
 
-impl Move {
-    fn name(&self) -> &'static str {
-        match self {
-            Move::Rock => "Rock",
-            Move::Paper => "Paper",
-            Move::Scissors => "Scissors",
-            Move::Lizard => "Lizard",
-            Move::Spock => "Spock",
-        }
-    }
+fn choose_saved_game() -> Option<MatchState> {
+    migrate_legacy_save();
 
-    fn all_for_ruleset(r: Ruleset) -> Vec<Move> {
-        match r {
-            Ruleset::Classic => vec![Move::Rock, Move::Paper, Move::Scissors],
-            Ruleset::Extended => vec![
-                Move::Rock,
-                Move::Paper,
-                Move::Scissors,
-                Move::Lizard,
-                Move::Spock,
-            ],
-        }
+    let mut saves: Vec<MatchState> = list_save_slots()
+        .into_iter()
+        .filter_map(|slot| match load_saved_game(slot) {
+            Ok(state) => Some(state),
+            Err(e) => {
+                abort_if_scripted(&e);
+                println!("{}", notice(&e));
+                None
+            }
+        })
+        .collect();
+
+    if saves.is_empty() {
+        abort_if_scripted("no saved game found");
+        println!("\n{}", t("saves.none"));
+        pause();
+        return None;
     }
-}
 
-fn ascii_move(mv: Move) -> &'static str {
-    match mv {
-        Move::Rock => r#"
-    _______
----'   ____)
-      (_____)
-      (_____)
-      (____)
----.__(___)
-"#,
-        Move::Paper => r#"
-     _______
----'   ____)____
-          ______)
-          _______)
-         _______)
----.__________)
-"#,
-        Move::Scissors => r#"
-    _______
----'   ____)____
-          ______)
-       __________)
-      (____)
----.__(___)
-"#,
-        Move::Lizard => r#"
-     __,---._
-    /        `.
-   |   .-"""-. |
-   |  /  _ _  \|
-    \ | | | | |
-     \| |_| |_||
-       \       /
-        `-.__.-'
-"#,
-        Move::Spock => r#"
-     🖖
-  Live long
-  and prosper
-"#,
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct RoundRecord {
-    round: u32,
-    p1_move: Move,
-    p2_move: Move,
-    winner: RoundWinner,
-}
-
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-enum RoundWinner {
-    Player1,
-    Player2,
-    Tie,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct MatchState {
-    config: GameConfig,
-    round_number: u32,
-    p1_round_wins: u32,
-    p2_round_wins: u32,
-    history: Vec<RoundRecord>,
-    human_recent: Vec<Move>,
-    turn: Turn,
-}
-
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-enum Turn {
-    WaitingP1,
-    WaitingP2,
-    Reveal,
-}
-
-impl MatchState {
-    fn new(config: GameConfig) -> Self {
-        let turn = match config.mode {
-            Mode::SinglePlayer => Turn::WaitingP1,
-            Mode::Multiplayer => Turn::WaitingP1,
-        };
-        Self {
-            config,
-            round_number: 1,
-            p1_round_wins: 0,
-            p2_round_wins: 0,
-            history: vec![],
-            human_recent: vec![],
-            turn,
+    clear_screen();
+    println!("{}\n", t("saves.title"));
+    for (i, st) in saves.iter().enumerate() {
+        let (p1_score, p2_score) = displayed_score(st);
+        let entry = tf(
+            "saves.entry",
+            &[
+                &st.save_slot.unwrap_or_default(),
+                &st.config.player1,
+                &st.config.player2,
+                &st.history.len(),
+                &p1_score,
+                &p2_score,
+            ],
+        );
+        menu_item(i + 1, entry);
+        if let Some(v) = st.migrated_from {
+            println!("   {}", tf("saves.migrated", &[&v]));
         }
     }
+    let back = saves.len() as i32 + 1;
+    menu_item(back, t("common.back"));
 
-    fn reset_for_rematch(&mut self) {
-        self.round_number = 1;
-        self.p1_round_wins = 0;
-        self.p2_round_wins = 0;
-        self.history.clear();
-        self.human_recent.clear();
-        self.turn = Turn::WaitingP1;
+    let choice = read_menu_choice(1, back);
+    if choice == back {
+        return None;
     }
+    Some(saves.swap_remove(choice as usize - 1))
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
-struct PlayerStats {
-    matches_played: u32,
-    matches_won: u32,
-    rounds_won: u32,
+// A script can't answer the menu that would come next, so it stops here with
+// a failing status instead.
+fn abort_if_scripted(message: &str) {
+    if input::scripted() {
+        eprintln!("rps: {}", message);
+        std::process::exit(1);
+    }
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
-struct Scoreboard {
-    players: HashMap<String, PlayerStats>,
+// Hosting needs an address, which --resume has no way to ask for.
+fn resume_slot(slot: u32) -> Result<MatchState, String> {
+    migrate_legacy_save();
+    let state = load_saved_game(slot)?;
+    if state.config.mode == Mode::Network {
+        return Err(format!(
+            "slot {} is a network match; resume it from the menu",
+            slot
+        ));
+    }
+    Ok(state)
 }
 
-impl Scoreboard {
-    fn load() -> Self {
-        let Ok(data) = fs::read_to_string(SCORE_FILE) else {
-            return Scoreboard::default();
-        };
-        serde_json::from_str(&data).unwrap_or_default()
+// Network saves are picked up again by hosting them; whoever connects has to
+// be the other player in the save.
+fn resume_network(state: MatchState) -> Option<MatchState> {
+    if state.config.mode != Mode::Network {
+        return Some(state);
     }
-
-    fn save(&self) {
-        if let Ok(json) = serde_json::to_string_pretty(self) {
-            let _ = fs::write(SCORE_FILE, json);
+    let addr = read_line(&format!("\n{}", t("network.host_prompt")));
+    match network::host(&addr, state) {
+        Ok(state) => Some(state),
+        Err(e) => {
+            println!("\n{}", e);
+            pause();
+            None
         }
     }
+}
 
-    fn ensure_player(&mut self, name: &str) {
-        self.players
-            .entry(name.to_string())
-            .or_insert_with(PlayerStats::default);
+// Stats from another machine's scoreboard are added to ours, never replace them.
+fn import_scoreboard(scoreboard: &mut Scoreboard) {
+    let path = read_line(&format!("\n{}", t("sb.import_prompt")));
+    if path.is_empty() {
+        return;
+    }
+    // Importing the file in use would double every count.
+    let same = fs::canonicalize(&path)
+        .ok()
+        .is_some_and(|p| fs::canonicalize(SCORE_FILE).is_ok_and(|ours| ours == p));
+    if same {
+        println!("{}", t("sb.import_same"));
+        pause();
+        return;
+    }
+    match Scoreboard::load_from(&path) {
+        Ok(other) => {
+            let (players, new, matches) = scoreboard.merge(other);
+            scoreboard.save();
+            println!("{}", tf("sb.imported", &[&players, &new, &matches]));
+        }
+        Err(e) => println!("{}", tf("sb.import_failed", &[&path, &e])),
     }
+    pause();
+}
 
-    fn add_match_result(
-        &mut self,
-        p1: &str,
-        p2: &str,
-        winner: Option<&str>,
-        p1_rounds: u32,
-        p2_rounds: u32,
-    ) {
-        self.ensure_player(p1);
-        self.ensure_player(p2);
+fn view_scoreboard(scoreboard: &mut Scoreboard, min_matches: u32) {
+    // Exports use whichever ordering was last looked at.
+    let mut key = SortKey::MatchesWon;
+    loop {
+        clear_screen();
 
-        {
-            let s1 = self.players.get_mut(p1).unwrap();
-            s1.matches_played += 1;
-            s1.rounds_won += p1_rounds;
+        if scoreboard.players.is_empty() {
+            println!("\n{}", t("sb.empty"));
+            println!("{}", t("sb.empty_hint"));
+            if !read_yes_no(&format!("\n{}", t("sb.import_offer"))) {
+                return;
+            }
+            import_scoreboard(scoreboard);
+            if scoreboard.players.is_empty() {
+                return;
+            }
+            continue;
         }
-        {
-            let s2 = self.players.get_mut(p2).unwrap();
-            s2.matches_played += 1;
-            s2.rounds_won += p2_rounds;
+
+        println!("{}", t("sb.title"));
+        let items = [
+            "sb.sort_mw",
+            "sb.sort_wr",
+            "sb.sort_rw",
+            "sb.sort_elo",
+            "sb.h2h",
+            "sb.details",
+            "sb.remove",
+            "sb.reset_all",
+            "sb.import",
+            "sb.export",
+            "sb.export_profile",
+            "common.back",
+        ];
+        for (i, item) in items.iter().enumerate() {
+            menu_item(i + 1, t(item));
         }
 
-        if let Some(w) = winner {
-            if let Some(sw) = self.players.get_mut(w) {
-                sw.matches_won += 1;
+        let choice = read_menu_choice(1, items.len() as i32);
+        key = match items[choice as usize - 1] {
+            "sb.sort_mw" => SortKey::MatchesWon,
+            "sb.sort_wr" => SortKey::WinRate,
+            "sb.sort_rw" => SortKey::RoundsWon,
+            "sb.sort_elo" => SortKey::Elo,
+            "sb.h2h" => {
+                view_head_to_head(scoreboard);
+                continue;
+            }
+            "sb.details" => {
+                view_player_details(scoreboard);
+                continue;
+            }
+            "sb.remove" => {
+                let name = read_line(&format!("\n{}", t("sb.remove_prompt")));
+                if scoreboard.remove_player(&name) {
+                    scoreboard.save();
+                    println!("{}", tf("sb.removed", &[&name]));
+                } else {
+                    println!("{}", tf("sb.no_player", &[&name]));
+                }
+                pause();
+                continue;
+            }
+            "sb.reset_all" => {
+                if read_yes_no(&format!("\n{}", t("sb.reset_confirm"))) {
+                    *scoreboard = Scoreboard::default();
+                    scoreboard.save();
+                    println!("{}", t("sb.reset_done"));
+                    pause();
+                }
+                continue;
+            }
+            "sb.import" => {
+                import_scoreboard(scoreboard);
+                continue;
+            }
+            "sb.export" => {
+                export_standings(scoreboard, key, min_matches);
+                continue;
+            }
+            "sb.export_profile" => {
+                export_profile(scoreboard);
+                continue;
             }
+            _ => return,
+        };
+        let (rows, provisional) = scoreboard_rows(scoreboard, key, min_matches);
+
+        clear_screen();
+        let name_width = layout::name_width(SCOREBOARD_STATS_WIDTH, NAME_WIDTH);
+        println!(
+            "{:<name_width$} {:>6} {:>6} {:>8} {:>10} {:>10} {:>7} {:>5} {:>6}",
+            t("col.player"),
+            t("col.mp"),
+            t("col.mw"),
+            t("col.rw"),
+            t("col.win_rate"),
+            t("col.round_win_rate"),
+            t("col.streak"),
+            t("col.best"),
+            t("col.elo")
+        );
+        println!("{}", layout::rule('-', name_width + SCOREBOARD_STATS_WIDTH));
+
+        let print_row = |(name, st, wr): &ScoreRow| {
+            // Matches from before rounds were counted have nothing to go on.
+            let round_rate = if st.rounds_played == 0 {
+                "-".to_string()
+            } else {
+                format!(
+                    "{:.0}%",
+                    st.rounds_won as f32 / st.rounds_played as f32 * 100.0
+                )
+            };
+            println!(
+                "{:<name_width$} {:>6} {:>6} {:>8} {:>9.0}% {:>10} {:>7} {:>5} {:>6.0}",
+                layout::fit(name, name_width),
+                st.matches_played,
+                st.matches_won,
+                st.rounds_won,
+                wr * 100.0,
+                round_rate,
+                streak_label(st.current_streak),
+                st.longest_win_streak,
+                st.elo
+            );
+        };
+        rows.iter().for_each(print_row);
+        if !provisional.is_empty() {
+            println!("\n{}", tf("sb.provisional", &[&min_matches]));
+            provisional.iter().for_each(print_row);
         }
+
+        pause();
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct SaveData {
-    state: MatchState,
-    scoreboard: Scoreboard,
+// Ranked rows, then the provisional ones sorting by win rate holds back.
+fn scoreboard_rows(
+    scoreboard: &Scoreboard,
+    key: SortKey,
+    min_matches: u32,
+) -> (Vec<ScoreRow>, Vec<ScoreRow>) {
+    let mut rows: Vec<ScoreRow> = scoreboard
+        .players
+        .iter()
+        .map(|(name, stats)| (name.clone(), stats.clone(), win_rate(stats)))
+        .collect();
+    sort_rows(&mut rows, key);
+    // A couple of lucky matches shouldn't top the win-rate board.
+    match key {
+        SortKey::WinRate => rows
+            .into_iter()
+            .partition(|r| r.1.matches_played >= min_matches),
+        _ => (rows, vec![]),
+    }
 }
 
-fn save_game(state: &MatchState, scoreboard: &Scoreboard) {
-    let data = SaveData {
-        state: state.clone(),
-        scoreboard: scoreboard.clone(),
+fn export_standings(scoreboard: &Scoreboard, key: SortKey, min_matches: u32) {
+    let path = read_line(&format!("\n{}", tf("sb.export_prompt", &[&STANDINGS_FILE])));
+    let path = if path.is_empty() {
+        STANDINGS_FILE.to_string()
+    } else {
+        path
     };
-    if let Ok(json) = serde_json::to_string_pretty(&data) {
-        let _ = fs::write(SAVE_FILE, json);
+
+    let (mut rows, provisional) = scoreboard_rows(scoreboard, key, min_matches);
+    rows.extend(provisional);
+    match fs::write(&path, standings_markdown(&rows)) {
+        Ok(()) => println!("{}", tf("sb.exported", &[&path, &rows.len()])),
+        Err(e) => println!("{}", tf("sb.export_failed", &[&path, &e])),
     }
+    pause();
 }
 
-fn load_saved_game() -> Result<(MatchState, Scoreboard), ()> {
-    let data = fs::read_to_string(SAVE_FILE).map_err(|_| ())?;
-    let sd: SaveData = serde_json::from_str(&data).map_err(|_| ())?;
-    Ok((sd.state, sd.scoreboard))
+fn export_profile(scoreboard: &Scoreboard) {
+    println!();
+    let name = read_line(t("sb.player_prompt"));
+    let Some(profile) = scoreboard.profile(&name) else {
+        println!("{}", tf("sb.no_player", &[&name]));
+        pause();
+        return;
+    };
+    // Spelled the way the scoreboard has it, not as typed.
+    let name = scoreboard.player_key(&name);
+
+    // Anything but letters and digits would make for an awkward file name.
+    let safe: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    let default = format!("rps_profile_{}.json", safe);
+    let path = read_line(&format!("\n{}", tf("sb.export_prompt", &[&default])));
+    let path = if path.is_empty() { default } else { path };
+    match profile.write_to(&path) {
+        Ok(()) => println!("{}", tf("sb.profile_exported", &[&name, &path])),
+        Err(e) => println!("{}", tf("sb.export_failed", &[&path, &e])),
+    }
+    pause();
 }
 
-fn clear_saved_game() {
-    let _ = fs::remove_file(SAVE_FILE);
+fn standings_markdown(rows: &[ScoreRow]) -> String {
+    if rows.is_empty() {
+        return format!("_{}_\n", t("sb.empty"));
+    }
+    let mut out = format!(
+        "| {} | {} | {} | {} | {} |\n|---|---:|---:|---:|---:|\n",
+        t("col.player"),
+        t("col.mp"),
+        t("col.mw"),
+        t("col.rw"),
+        t("col.win_rate")
+    );
+    for (name, st, wr) in rows {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {:.0}% |\n",
+            name.replace('|', "\\|"),
+            st.matches_played,
+            st.matches_won,
+            st.rounds_won,
+            wr * 100.0
+        ));
+    }
+    out
 }
 
-fn view_scoreboard(scoreboard: &Scoreboard) {
-    loop {
-        clear_screen();
+fn view_player_details(scoreboard: &Scoreboard) {
+    println!();
+    let name = read_line(t("sb.player_prompt"));
 
-        if scoreboard.players.is_empty() {
-            println!("\nNo matches played yet.");
-            println!("Play some matches so that the score can be displayed.");
-            pause();
-            return;
-        }
+    clear_screen();
+    let Some(stats) = scoreboard.player(&name) else {
+        println!("{}", tf("sb.no_player", &[&name]));
+        pause();
+        return;
+    };
 
-        println!("Scoreboard");
-        println!("1) Sort by matches won");
-        println!("2) Sort by win rate");
-        println!("3) Sort by rounds won");
-        println!("4) Back");
+    println!("{}\n", name);
+    let label_width = layout::name_width(DETAILS_STATS_WIDTH, NAME_WIDTH);
+    println!(
+        "{:<label_width$} {:>6} {:>6} {:>8} {:>10}",
+        "",
+        t("col.mp"),
+        t("col.mw"),
+        t("col.rw"),
+        t("col.win_rate")
+    );
+    println!("{}", layout::rule('-', label_width + DETAILS_STATS_WIDTH));
+    print_stats_row(t("sb.overall"), stats, label_width);
 
-        let choice = read_menu_choice(1, 4);
-        if choice == 4 {
-            return;
-        }
+    let mut rulesets: Vec<(&String, &PlayerStats)> = stats.by_ruleset.iter().collect();
+    rulesets.sort_by(|a, b| a.0.cmp(b.0));
+    for (ruleset, st) in rulesets {
+        print_stats_row(ruleset, st, label_width);
+    }
 
-        let mut rows: Vec<(String, PlayerStats, f32)> = scoreboard
-            .players
-            .iter()
-            .map(|(name, stats)| {
-                let win_rate = if stats.matches_played == 0 {
-                    0.0
-                } else {
-                    stats.matches_won as f32 / stats.matches_played as f32
-                };
-                (name.clone(), stats.clone(), win_rate)
+    let total: u32 = stats.move_counts.values().sum();
+    if total > 0 {
+        let parts: Vec<String> = Move::all_for_ruleset(&Ruleset::Extended)
+            .into_iter()
+            .filter_map(|m| {
+                let n = *stats.move_counts.get(&m)?;
+                let share = n as f32 / total as f32 * 100.0;
+                Some(format!(
+                    "{} {:.0}%",
+                    move_name(&Ruleset::Extended, m),
+                    share
+                ))
             })
             .collect();
+        println!("\n{}", tf("sb.moves", &[&parts.join(", ")]));
+    }
 
-        match choice {
-            1 => rows.sort_by(|a, b| b.1.matches_won.cmp(&a.1.matches_won)),
-            2 => rows.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap()),
-            3 => rows.sort_by(|a, b| b.1.rounds_won.cmp(&a.1.rounds_won)),
-            _ => {}
-        }
+    if stats.series_played > 0 {
+        let line = tf("sb.series", &[&stats.series_won, &stats.series_played]);
+        println!("\n{}", line);
+    }
 
-        clear_screen();
-        println!(
-            "{:<20} {:>6} {:>6} {:>8} {:>10}",
-            "Player", "MP", "MW", "RW", "    Win Rate"
-        );
-        println!("{}", "-".repeat(56));
+    if stats.play_time_secs > 0 {
+        let time = format_duration(stats.play_time_secs);
+        println!("\n{}", tf("sb.play_time", &[&time]));
+    }
 
-        for (name, st, wr) in rows {
-            println!(
-                "{:<20} {:>6} {:>6} {:>8} {:>9.0}%",
-                name,
-                st.matches_played,
-                st.matches_won,
-                st.rounds_won,
-                wr * 100.0
-            );
-        }
+    if let (Some(avg), Some(fastest)) = (stats.average_response(), stats.fastest_response_ms) {
+        let fastest = Duration::from_millis(fastest);
+        let line = tf("sb.response", &[&format_secs(avg), &format_secs(fastest)]);
+        println!("\n{}", line);
+    }
+
+    if !stats.achievements.is_empty() {
+        let mut names: Vec<&str> = stats
+            .achievements
+            .iter()
+            .map(|&a| lang::achievement_name(a))
+            .collect();
+        names.sort();
+        println!("\n{}", tf("sb.achievements", &[&names.join(", ")]));
+    }
+
+    pause();
+}
+
+fn print_stats_row(label: &str, st: &PlayerStats, label_width: usize) {
+    let win_rate = if st.matches_played == 0 {
+        0.0
+    } else {
+        st.matches_won as f32 / st.matches_played as f32
+    };
+    println!(
+        "{:<label_width$} {:>6} {:>6} {:>8} {:>9.0}%",
+        layout::fit(label, label_width),
+        st.matches_played,
+        st.matches_won,
+        st.rounds_won,
+        win_rate * 100.0
+    );
+}
+
+fn view_head_to_head(scoreboard: &Scoreboard) {
+    println!();
+    let a = read_line(t("h2h.first"));
+    let b = read_line(t("h2h.second"));
 
+    clear_screen();
+    let known = |n: &str| scoreboard.player(n).is_some();
+    if canonical_name(&a) == canonical_name(&b) || !known(&a) || !known(&b) {
+        println!("{}", t("h2h.invalid"));
         pause();
+        return;
+    }
+    let (a, b) = (scoreboard.player_key(&a), scoreboard.player_key(&b));
+
+    let (a_wins, b_wins, ties) = scoreboard.head_to_head(&a, &b);
+    println!("{}\n", t("h2h.title"));
+    if a_wins + b_wins + ties == 0 {
+        println!("{}", tf("h2h.none", &[&a, &b]));
+    } else {
+        println!("{} {} - {} {}", a, a_wins, b_wins, b);
+        println!("{}", tf("h2h.ties", &[&ties]));
+    }
+    pause();
+}
+
+// One Classic round against Normal with nothing asked; only the display
+// settings come from the saved defaults.
+fn quick_play_config(defaults: &Defaults) -> GameConfig {
+    GameConfig {
+        player1: "Player".to_string(),
+        player2: computer_name(Difficulty::Normal, true),
+        difficulty: Some(Difficulty::Normal),
+        rng_seed: seed_from_env(),
+        use_color: defaults.use_color.unwrap_or(true),
+        show_ascii: defaults.show_ascii.unwrap_or(true),
+        show_emoji: defaults.show_emoji.unwrap_or(false),
+        ai_memory: defaults.ai_memory.unwrap_or(DEFAULT_AI_MEMORY),
+        ..Default::default()
     }
 }
 
-fn new_game_setup() -> GameConfig {
+fn new_game_setup(defaults: &Defaults) -> GameConfig {
     clear_screen();
 
-    println!("==============================");
-    println!("        New Game Setup");
-    println!("==============================\n");
+    print_heading(t("setup.title"));
 
-    println!("Mode:");
-    println!("1) Single-player");
-    println!("2) Multiplayer");
+    println!("{}", t("setup.mode"));
+    menu_item(1, t("setup.single"));
+    menu_item(2, t("setup.multi"));
 
     let mode = match read_menu_choice(1, 2) {
         1 => Mode::SinglePlayer,
@@ -509,148 +901,315 @@ fn new_game_setup() -> GameConfig {
     };
 
     let player1_label = match mode {
-        Mode::SinglePlayer => "Player name: ",
-        Mode::Multiplayer => "Player 1 name: ",
+        Mode::SinglePlayer => t("setup.player_name"),
+        Mode::Multiplayer | Mode::Network => t("setup.p1_name"),
     };
 
     println!();
 
-    let player1 = loop {
-        let s = read_line(player1_label);
-        if !s.is_empty() {
-            break s;
-        }
-        println!("Name can't be empty.");
-    };
+    let player1 = read_name(player1_label, defaults.player1.as_deref(), None);
 
     let player2 = match mode {
-        Mode::SinglePlayer => "Computer".to_string(),
-        Mode::Multiplayer => loop {
-            let s = read_line("Player 2 name: ");
-            if !s.is_empty() && s != player1 {
-                break s;
-            }
-            println!("Name can't be empty and must be different from Player 1.");
-        },
+        // Named once the difficulty is known.
+        Mode::SinglePlayer => String::new(),
+        Mode::Multiplayer | Mode::Network => {
+            let last = defaults.player2.as_deref().filter(|&p| p != player1);
+            read_name(t("setup.p2_name"), last, Some(&player1))
+        }
     };
 
-    println!("\nRuleset:");
-    println!("1) Classic");
-    println!("2) Extended");
+    println!("\n{}", t("setup.ruleset"));
+    let ruleset = read_ruleset(defaults.ruleset.as_ref());
 
-    let ruleset = match read_menu_choice(1, 2) {
-        1 => Ruleset::Classic,
-        _ => Ruleset::Extended,
-    };
+    println!("\n{}", t("setup.format"));
+    let format = read_format(defaults.format);
 
-    println!("\nFormat:");
-    println!("1) Single round");
-    println!("2) Best of N");
-    println!("3) First to K wins");
+    let difficulty = match mode {
+        Mode::SinglePlayer => {
+            println!("\n{}", t("setup.difficulty"));
+            Some(read_difficulty(defaults.difficulty))
+        }
+        Mode::Multiplayer | Mode::Network => None,
+    };
+    let show_ai_hints = matches!(difficulty, Some(Difficulty::Hard))
+        && read_yes_no(&format!("\n{}", t("setup.ai_hints")));
+
+    let move_timeout_secs = match mode {
+        Mode::SinglePlayer => loop {
+            let s = read_line(&format!("\n{}", t("setup.timer")));
+            if s.is_empty() {
+                break None;
+            }
+            if let Ok(v) = s.parse::<u64>() {
+                if v >= 1 {
+                    break Some(v);
+                }
+            }
+            println!("{}", t("common.invalid"));
+        },
+        Mode::Multiplayer | Mode::Network => None,
+    };
 
-    let format = match read_menu_choice(1, 3) {
-        1 => MatchFormat::SingleRound,
-        2 => {
-            let n = loop {
-                let s = read_line("Enter N (odd number >= 1): ");
-                if let Ok(v) = s.parse::<u32>() {
-                    if v >= 1 && v % 2 == 1 {
-                        break v;
-                    }
+    let advanced = mode == Mode::SinglePlayer && read_yes_no(&format!("\n{}", t("setup.advanced")));
+    let default_memory = defaults.ai_memory.unwrap_or(DEFAULT_AI_MEMORY);
+    let ai_memory = if advanced {
+        loop {
+            let s = read_line(&tf("setup.ai_memory", &[&MAX_AI_MEMORY, &default_memory]));
+            if s.is_empty() {
+                break default_memory;
+            }
+            if let Ok(v) = s.parse::<usize>() {
+                if (1..=MAX_AI_MEMORY).contains(&v) {
+                    break v;
                 }
-                println!("Invalid.");
-            };
-            MatchFormat::BestOfN(n)
+            }
+            println!("{}", t("common.invalid"));
         }
-        _ => {
-            let k = loop {
-                let s = read_line("Enter K (>= 1): ");
-                if let Ok(v) = s.parse::<u32>() {
-                    if v >= 1 {
-                        break v;
-                    }
-                }
-                println!("Invalid.");
-            };
-            MatchFormat::FirstToK(k)
+    } else {
+        default_memory
+    };
+    let counter_chance = if advanced && matches!(difficulty, Some(Difficulty::Normal)) {
+        loop {
+            let s = read_line(&tf("setup.counter_chance", &[&DEFAULT_COUNTER_CHANCE]));
+            if s.is_empty() {
+                break DEFAULT_COUNTER_CHANCE;
+            }
+            match s.parse::<u8>() {
+                Ok(v) if v <= 100 => break v,
+                _ => println!("{}", t("common.invalid")),
+            }
         }
+    } else {
+        DEFAULT_COUNTER_CHANCE
+    };
+    let weight_recent = advanced
+        && matches!(difficulty, Some(Difficulty::Hard))
+        && read_yes_no(t("setup.weight_recent"));
+    let reveal_difficulty = !advanced || read_yes_no(t("setup.reveal_difficulty"));
+    // A persona's name would give a hidden difficulty away.
+    let offered = difficulty
+        .map(Persona::for_difficulty)
+        .filter(|p| reveal_difficulty && canonical_name(p.name()) != canonical_name(&player1));
+    let persona = match offered {
+        Some(p) if read_yes_no(&format!("\n{}", tf("setup.persona", &[&p.name()]))) => Some(p),
+        _ => None,
+    };
+    let player2 = match (persona, difficulty) {
+        (Some(p), _) => p.name().to_string(),
+        (None, Some(d)) => computer_name(d, reveal_difficulty),
+        (None, None) => player2,
     };
 
-    let difficulty = match mode {
-        Mode::SinglePlayer => {
-            println!("\nDifficulty:");
-            println!("1) Easy");
-            println!("2) Normal");
-            println!("3) Hard");
-
-            let diff = match read_menu_choice(1, 3) {
-                1 => Difficulty::Easy,
-                2 => Difficulty::Normal,
-                _ => Difficulty::Hard,
-            };
-            Some(diff)
-        }
-        Mode::Multiplayer => None,
+    let replay_ties = !matches!(format, MatchFormat::SingleRound)
+        && read_yes_no(&format!("\n{}", t("setup.replay_ties")));
+    // With nothing but decisive rounds recorded there's no streak of ties.
+    let max_consecutive_ties = match format {
+        _ if replay_ties => None,
+        MatchFormat::SingleRound => None,
+        _ => loop {
+            let s = read_line(&format!("\n{}", t("setup.sudden_death")));
+            if s.is_empty() {
+                break None;
+            }
+            if let Ok(v) = s.parse::<u32>() {
+                if v >= 1 {
+                    break Some(v);
+                }
+            }
+            println!("{}", t("common.invalid"));
+        },
     };
 
-    GameConfig {
-        player1,
-        player2,
-        mode,
-        ruleset,
-        format,
+    let max_rounds = match format {
+        MatchFormat::SingleRound => None,
+        _ => loop {
+            let s = read_line(&format!("\n{}", t("setup.max_rounds")));
+            if s.is_empty() {
+                break None;
+            }
+            match s.parse::<u32>() {
+                Ok(v) if v >= 1 => break Some(v),
+                _ => println!("{}", t("common.invalid")),
+            }
+        },
+    };
+
+    let handicap = match format {
+        MatchFormat::SingleRound => (0, 0),
+        _ => loop {
+            let prompt = tf("setup.handicap", &[&player1, &player2]);
+            let s = read_line(&format!("\n{}", prompt));
+            if s.is_empty() {
+                break (0, 0);
+            }
+            let parsed = s
+                .split_once('-')
+                .and_then(|(a, b)| Some((a.trim().parse().ok()?, b.trim().parse().ok()?)));
+            if let Some(h) = parsed.filter(|&h| valid_handicap(&format, h)) {
+                break h;
+            }
+            println!("{}", t("common.invalid"));
+        },
+    };
+
+    let commit_moves =
+        mode == Mode::Multiplayer && read_yes_no(&format!("\n{}", t("setup.commit")));
+    let shared_screen =
+        mode == Mode::Multiplayer && read_yes_no(&format!("\n{}", t("setup.shared_screen")));
+    let ranked = read_yes_no(&format!("\n{}", t("setup.ranked")));
+
+    println!();
+    let use_color = read_yes_no_or(t("setup.colors"), defaults.use_color);
+    let graphics = defaults.show_ascii.zip(defaults.show_emoji);
+    let (show_ascii, show_emoji) = read_graphics(graphics);
+
+    GameConfig {
+        player1,
+        player2,
+        mode,
+        ruleset,
+        format,
         difficulty,
+        rng_seed: seed_from_env(),
+        use_color,
+        show_ascii,
+        show_emoji,
+        move_timeout_secs,
+        max_consecutive_ties,
+        commit_moves,
+        handicap,
+        ai_memory,
+        reveal_difficulty,
+        event_log: None,
+        show_ai_hints,
+        counter_chance,
+        persona,
+        replay_ties,
+        max_rounds,
+        ranked,
+        weight_recent,
+        shared_screen,
+    }
+}
+
+// The second name can't repeat the first.
+fn read_name(prompt: &str, default: Option<&str>, other: Option<&str>) -> String {
+    if let Some(d) = default {
+        print_default(d);
+    }
+    loop {
+        let s = read_line(prompt);
+        if s.is_empty() {
+            if let Some(d) = default {
+                return d.to_string();
+            }
+        } else if other.is_none_or(|o| canonical_name(o) != canonical_name(&s)) {
+            return s;
+        }
+        match other {
+            Some(_) => println!("{}", t("setup.p2_invalid")),
+            None => println!("{}", t("setup.name_empty")),
+        }
+    }
+}
+
+// Works in (show_ascii, show_emoji) pairs.
+fn read_graphics(default: Option<(bool, bool)>) -> (bool, bool) {
+    let options = [
+        ("graphics.none", (false, false)),
+        ("graphics.ascii", (true, false)),
+        ("graphics.emoji", (false, true)),
+    ];
+    println!("\n{}", t("setup.graphics"));
+    for (i, (key, _)) in options.iter().enumerate() {
+        menu_item(i + 1, t(key));
+    }
+    if let Some(d) = default {
+        if let Some((key, _)) = options.iter().find(|(_, g)| *g == d) {
+            print_default(t(key));
+        }
+    }
+    match read_menu_choice_or(1, 3, default.is_some()) {
+        Some(n) => options[n as usize - 1].1,
+        None => default.unwrap_or_default(),
     }
 }
 
 fn run_match(state: &mut MatchState, scoreboard: &mut Scoreboard) {
-    scoreboard.ensure_player(&state.config.player1);
-    scoreboard.ensure_player(&state.config.player2);
+    color::set_enabled(should_use_color(&state.config));
+    play_match(state, scoreboard);
+    network::disconnect();
+    interrupt::clear();
+    color::set_enabled(true);
+}
+
+fn play_match(state: &mut MatchState, scoreboard: &mut Scoreboard) {
+    if state.config.ranked {
+        scoreboard.ensure_player(&state.config.player1);
+        scoreboard.ensure_player(&state.config.player2);
+    }
 
     let mut pending_p1: Option<Move> = None;
     let mut pending_p2: Option<Move> = None;
+    // (hash, nonce) for each pending move when commitments are on.
+    let mut commit_p1: Option<(String, String)> = None;
+    let mut commit_p2: Option<(String, String)> = None;
 
     let mut skip_pre_round = false;
 
     loop {
+        interrupt::track(state, scoreboard);
+
         if !skip_pre_round {
             clear_screen();
             print_match_header(state);
 
-            println!("\nQuick actions:");
+            println!("\n{}", t("match.quick"));
 
             let is_single_round_start = matches!(state.config.format, MatchFormat::SingleRound)
                 && state.history.is_empty()
                 && state.round_number == 1;
 
-            if is_single_round_start {
-                println!("1) Continue to the 1st and only round");
-                println!("2) Return to main menu");
+            let display_round = if state.history.is_empty() {
+                state.round_number
+            } else {
+                state.round_number + 1
+            };
+
+            let continue_line = tf("match.continue_round", &[&display_round]);
+            if state.in_event() {
+                menu_item(1, continue_line);
+                menu_item(2, leave_event(state));
                 let pre = read_menu_choice(1, 2);
                 if pre == 2 {
                     scoreboard.save();
                     return;
                 }
-            } else {
-                let display_round = if state.history.is_empty() {
-                    state.round_number
-                } else {
-                    state.round_number + 1
-                };
-
-                println!("1) Continue to round {}", display_round);
-                println!("2) Save now (return to main menu)");
-                println!("3) Return to main menu without saving");
-
-                let pre = read_menu_choice(1, 3);
+            } else if is_single_round_start {
+                menu_item(1, t("match.continue_only"));
+                menu_item(2, t("match.menu"));
+                let pre = read_menu_choice(1, 2);
                 if pre == 2 {
-                    save_game(state, scoreboard);
                     scoreboard.save();
                     return;
                 }
-                if pre == 3 {
-                    scoreboard.save();
-                    return;
+            } else {
+                menu_item(1, continue_line);
+                menu_item(2, t("match.save_now"));
+                menu_item(3, t("match.menu_no_save"));
+
+                match read_menu_choice(1, 3) {
+                    2 => {
+                        save_match(state, scoreboard);
+                        scoreboard.save();
+                        return;
+                    }
+                    3 if confirm_discard(state) => {
+                        scoreboard.save();
+                        return;
+                    }
+                    3 => continue,
+                    _ => {}
                 }
             }
         } else {
@@ -661,39 +1220,75 @@ fn run_match(state: &mut MatchState, scoreboard: &mut Scoreboard) {
             state.turn = Turn::WaitingP1;
         }
 
-        let allow_save = !matches!(state.config.format, MatchFormat::SingleRound);
+        let allow_save =
+            !matches!(state.config.format, MatchFormat::SingleRound) && !state.in_event();
 
         match state.config.mode {
             Mode::SinglePlayer => {
-                let p1 = loop {
-                    clear_screen();
+                clear_screen();
 
-                    println!(
-                        "Accepted inputs: {}",
-                        accepted_inputs_line(state.config.ruleset)
-                    );
-                    if allow_save {
-                        println!("Type 'save' to save now and return to menu.");
+                println!(
+                    "{}",
+                    tf(
+                        "match.accepted",
+                        &[&accepted_inputs_line(&state.config.ruleset)]
+                    )
+                );
+                if allow_save {
+                    println!("{}", t("match.save_hint"));
+                }
+                println!("{}", t("match.command_hint"));
+                print_sudden_death_notice(state);
+
+                let timeout = move_timeout(&state.config);
+                let mut started = response_timer();
+                let mut timed_out = false;
+                let input = match read_move_player(
+                    &state.config.player1,
+                    &state.config.ruleset,
+                    allow_save,
+                    timeout,
+                ) {
+                    Some(input) => input,
+                    None => {
+                        started = None;
+                        timed_out = true;
+                        let all = Move::all_for_ruleset(&state.config.ruleset);
+                        let mv = random_from(&all, &mut state.rng);
+                        let name = move_name(&state.config.ruleset, mv);
+                        println!("\n{}", tf("match.times_up", &[&name]));
+                        read_line(t("common.enter_continue"));
+                        MoveInput::Move(mv)
                     }
-
-                    match read_move_player_or_save(
-                        &state.config.player1,
-                        state.config.ruleset,
-                        allow_save,
-                    ) {
-                        MoveOrSave::Save => {
-                            save_game(state, scoreboard);
-                            scoreboard.save();
-                            return;
-                        }
-                        MoveOrSave::Move(mv) => break mv,
+                };
+                let p1 = match move_or_command(state, scoreboard, input, allow_save) {
+                    Ok(mv) => mv,
+                    Err(Command::Leave) => return,
+                    Err(Command::Reprompt) => {
+                        skip_pre_round = true;
+                        continue;
                     }
                 };
+                record_response(&state.config, scoreboard, &state.config.player1, started);
+                let decision = ai_move(state, p1);
+                let p2 = decision.chosen;
+                // The move picked when time ran out isn't one the player made.
+                if !timed_out {
+                    record_moves(state, scoreboard, p1, p2);
+                }
 
-                let p2 = ai_move(state, p1);
-
-                let winner = decide_winner(state.config.ruleset, p1, p2);
+                countdown(&state.config);
+                let winner = decide_winner(&state.config.ruleset, p1, p2);
+                if matches!(winner, RoundWinner::Tie) && tie_is_replayed(state) {
+                    log_replayed_tie(state, p1, p2);
+                    clear_screen();
+                    print_replayed_tie(state, p1, p2);
+                    skip_pre_round = true;
+                    continue;
+                }
                 apply_round(state, p1, p2, winner);
+                log_last_round(state);
+                interrupt::track(state, scoreboard);
 
                 let is_single_round = matches!(state.config.format, MatchFormat::SingleRound);
 
@@ -708,6 +1303,9 @@ fn run_match(state: &mut MatchState, scoreboard: &mut Scoreboard) {
                 } else {
                     clear_screen();
                     print_round_summary(state, p1, p2, winner);
+                    if let Some(hint) = ai_hint(&state.config, &decision) {
+                        println!("{}", hint);
+                    }
 
                     if let Some(match_winner) = check_match_winner(state) {
                         let action = handle_match_end(state, scoreboard, match_winner);
@@ -724,7 +1322,7 @@ fn run_match(state: &mut MatchState, scoreboard: &mut Scoreboard) {
                             continue;
                         }
                         AfterRoundAction::SaveAndMenu => {
-                            save_game(state, scoreboard);
+                            save_match(state, scoreboard);
                             scoreboard.save();
                             return;
                         }
@@ -732,90 +1330,189 @@ fn run_match(state: &mut MatchState, scoreboard: &mut Scoreboard) {
                             scoreboard.save();
                             return;
                         }
+                        AfterRoundAction::Concede(winner) => {
+                            state.conceded_to = Some(winner);
+                            match handle_match_end(state, scoreboard, winner) {
+                                AfterMatchAction::ContinueMatch => continue,
+                                AfterMatchAction::MainMenu => return,
+                            }
+                        }
                     }
                 }
             }
 
             Mode::Multiplayer => {
-                if pending_p1.is_none() {
+                // No handoff screens: the two prompts share a screen and
+                // nothing shows who answered when until both have.
+                if state.config.shared_screen && pending_p2.is_none() {
                     clear_screen();
-                    println!("{}'s turn", state.config.player1);
                     println!(
-                        "Accepted inputs: {}",
-                        accepted_inputs_line(state.config.ruleset)
+                        "{}",
+                        tf(
+                            "match.accepted",
+                            &[&accepted_inputs_line(&state.config.ruleset)]
+                        )
                     );
                     if allow_save {
-                        println!("Type 'save' to save now and return to menu.");
+                        println!("{}", t("match.save_hint"));
+                    }
+                    println!("{}", t("match.command_hint"));
+                    print_sudden_death_notice(state);
+                    println!();
+
+                    if pending_p1.is_none() {
+                        let prompt = tf("match.hidden_prompt", &[&state.config.player1]);
+                        let started = response_timer();
+                        let input = read_move_hidden(&prompt, &state.config.ruleset, allow_save);
+                        let mv = match move_or_command(state, scoreboard, input, allow_save) {
+                            Ok(mv) => mv,
+                            Err(Command::Leave) => return,
+                            Err(Command::Reprompt) => {
+                                skip_pre_round = true;
+                                continue;
+                            }
+                        };
+                        pending_p1 = Some(mv);
+                        record_response(&state.config, scoreboard, &state.config.player1, started);
+                        commit_p1 = commit_move(state, mv);
+                        state.turn = Turn::WaitingP2;
+                    } else {
+                        println!("{}", tf("match.locked", &[&state.config.player1]));
                     }
 
-                    match read_move_hidden_or_save(
-                        &state.config.player1,
-                        state.config.ruleset,
-                        allow_save,
-                    ) {
-                        MoveOrSave::Save => {
-                            save_game(state, scoreboard);
-                            scoreboard.save();
-                            return;
+                    let prompt = tf("match.hidden_prompt", &[&state.config.player2]);
+                    let started = response_timer();
+                    let input = read_move_hidden(&prompt, &state.config.ruleset, allow_save);
+                    let mv = match move_or_command(state, scoreboard, input, allow_save) {
+                        Ok(mv) => mv,
+                        Err(Command::Leave) => return,
+                        Err(Command::Reprompt) => {
+                            skip_pre_round = true;
+                            continue;
                         }
-                        MoveOrSave::Move(mv) => {
-                            pending_p1 = Some(mv);
-                            state.turn = Turn::WaitingP2;
-
-                            clear_screen();
-                            println!();
-                            println!("{} locked in.", state.config.player1);
-                            println!();
-                            println!("Press Enter to pass to {}.", state.config.player2);
-
-                            let mut s = String::new();
-                            let _ = io::stdin().read_line(&mut s);
+                    };
+                    pending_p2 = Some(mv);
+                    record_response(&state.config, scoreboard, &state.config.player2, started);
+                    commit_p2 = commit_move(state, mv);
+                    state.turn = Turn::Reveal;
+
+                    println!("\n{}", t("match.both_ready"));
+                    if let (Some((h1, _)), Some((h2, _))) = (&commit_p1, &commit_p2) {
+                        println!("  {}: {}", state.config.player1, h1);
+                        println!("  {}: {}", state.config.player2, h2);
+                    }
+                    read_line(&format!("\n{}", t("match.reveal")));
+                }
+
+                if pending_p1.is_none() {
+                    clear_screen();
+                    println!("{}", tf("match.turn", &[&state.config.player1]));
+                    println!(
+                        "{}",
+                        tf(
+                            "match.accepted",
+                            &[&accepted_inputs_line(&state.config.ruleset)]
+                        )
+                    );
+                    if allow_save {
+                        println!("{}", t("match.save_hint"));
+                    }
+                    println!("{}", t("match.command_hint"));
+                    print_sudden_death_notice(state);
+
+                    let started = response_timer();
+                    let input = read_move_hidden("", &state.config.ruleset, allow_save);
+                    let mv = match move_or_command(state, scoreboard, input, allow_save) {
+                        Ok(mv) => mv,
+                        Err(Command::Leave) => return,
+                        Err(Command::Reprompt) => {
+                            skip_pre_round = true;
+                            continue;
                         }
+                    };
+                    pending_p1 = Some(mv);
+                    record_response(&state.config, scoreboard, &state.config.player1, started);
+                    commit_p1 = commit_move(state, mv);
+                    state.turn = Turn::WaitingP2;
+
+                    clear_screen();
+                    println!();
+                    println!("{}", tf("match.locked", &[&state.config.player1]));
+                    if let Some((hash, _)) = &commit_p1 {
+                        println!("{}", tf("commit.hash", &[&hash]));
                     }
+                    println!();
+                    println!("{}", tf("match.pass_to", &[&state.config.player2]));
+
+                    let _ = input::line();
                 }
 
                 if pending_p2.is_none() {
                     clear_screen();
-                    println!("{}'s turn", state.config.player2);
+                    println!("{}", tf("match.turn", &[&state.config.player2]));
                     println!(
-                        "Accepted inputs: {}",
-                        accepted_inputs_line(state.config.ruleset)
+                        "{}",
+                        tf(
+                            "match.accepted",
+                            &[&accepted_inputs_line(&state.config.ruleset)]
+                        )
                     );
                     if allow_save {
-                        println!("Type 'save' to save now and return to menu.");
+                        println!("{}", t("match.save_hint"));
                     }
-
-                    match read_move_hidden_or_save(
-                        &state.config.player2,
-                        state.config.ruleset,
-                        allow_save,
-                    ) {
-                        MoveOrSave::Save => {
-                            save_game(state, scoreboard);
-                            scoreboard.save();
-                            return;
-                        }
-                        MoveOrSave::Move(mv) => {
-                            pending_p2 = Some(mv);
-                            state.turn = Turn::Reveal;
-
-                            clear_screen();
-                            println!();
-                            println!("{} locked in.", state.config.player2);
-                            println!();
-                            println!("Both moves are locked in.");
-                            println!();
-                            println!("Press Enter to reveal the result.");
-
-                            let mut s = String::new();
-                            let _ = io::stdin().read_line(&mut s);
+                    println!("{}", t("match.command_hint"));
+                    print_sudden_death_notice(state);
+
+                    let started = response_timer();
+                    let input = read_move_hidden("", &state.config.ruleset, allow_save);
+                    let mv = match move_or_command(state, scoreboard, input, allow_save) {
+                        Ok(mv) => mv,
+                        Err(Command::Leave) => return,
+                        Err(Command::Reprompt) => {
+                            skip_pre_round = true;
+                            continue;
                         }
+                    };
+                    pending_p2 = Some(mv);
+                    record_response(&state.config, scoreboard, &state.config.player2, started);
+                    commit_p2 = commit_move(state, mv);
+                    state.turn = Turn::Reveal;
+
+                    clear_screen();
+                    println!();
+                    println!("{}", tf("match.locked", &[&state.config.player2]));
+                    println!();
+                    println!("{}", t("match.both_locked"));
+                    if let (Some((h1, _)), Some((h2, _))) = (&commit_p1, &commit_p2) {
+                        println!("  {}: {}", state.config.player1, h1);
+                        println!("  {}: {}", state.config.player2, h2);
                     }
+                    println!();
+                    println!("{}", t("match.reveal"));
+
+                    let _ = input::line();
                 }
 
                 if let (Some(p1), Some(p2)) = (pending_p1, pending_p2) {
-                    let winner = decide_winner(state.config.ruleset, p1, p2);
+                    if let (Some(c1), Some(c2)) = (commit_p1.take(), commit_p2.take()) {
+                        reveal_commitments(state, (p1, c1), (p2, c2));
+                    }
+                    record_moves(state, scoreboard, p1, p2);
+
+                    countdown(&state.config);
+                    let winner = decide_winner(&state.config.ruleset, p1, p2);
+                    if matches!(winner, RoundWinner::Tie) && tie_is_replayed(state) {
+                        log_replayed_tie(state, p1, p2);
+                        pending_p1 = None;
+                        pending_p2 = None;
+                        clear_screen();
+                        print_replayed_tie(state, p1, p2);
+                        skip_pre_round = true;
+                        continue;
+                    }
                     apply_round(state, p1, p2, winner);
+                    log_last_round(state);
+                    interrupt::track(state, scoreboard);
 
                     clear_screen();
                     print_round_summary(state, p1, p2, winner);
@@ -838,7 +1535,7 @@ fn run_match(state: &mut MatchState, scoreboard: &mut Scoreboard) {
                             continue;
                         }
                         AfterRoundAction::SaveAndMenu => {
-                            save_game(state, scoreboard);
+                            save_match(state, scoreboard);
                             scoreboard.save();
                             return;
                         }
@@ -846,542 +1543,1791 @@ fn run_match(state: &mut MatchState, scoreboard: &mut Scoreboard) {
                             scoreboard.save();
                             return;
                         }
+                        AfterRoundAction::Concede(winner) => {
+                            state.conceded_to = Some(winner);
+                            match handle_match_end(state, scoreboard, winner) {
+                                AfterMatchAction::ContinueMatch => continue,
+                                AfterMatchAction::MainMenu => return,
+                            }
+                        }
                     }
                 }
             }
-        }
-    }
-}
-
-fn apply_round(state: &mut MatchState, p1: Move, p2: Move, winner: RoundWinner) {
-    match winner {
-        RoundWinner::Player1 => state.p1_round_wins += 1,
-        RoundWinner::Player2 => state.p2_round_wins += 1,
-        RoundWinner::Tie => {}
-    }
 
-    state.history.push(RoundRecord {
-        round: state.round_number,
-        p1_move: p1,
-        p2_move: p2,
-        winner,
-    });
-}
+            Mode::Network => {
+                clear_screen();
+                println!(
+                    "{}",
+                    tf(
+                        "match.accepted",
+                        &[&accepted_inputs_line(&state.config.ruleset)]
+                    )
+                );
+                if allow_save {
+                    println!("{}", t("match.save_hint"));
+                }
+                println!("{}", t("match.command_hint"));
+                print_sudden_death_notice(state);
 
-fn after_round_menu(state: &MatchState) -> AfterRoundAction {
-    loop {
-        println!("\nOptions:");
-        println!("1) Next round");
-        println!("2) View match history");
-        println!("3) Save now (return to main menu)");
-        println!("4) Return to main menu without saving");
+                let local_name = if network::local_is_p1() {
+                    state.config.player1.clone()
+                } else {
+                    state.config.player2.clone()
+                };
+                let started = response_timer();
+                // Without a timeout there's always an input.
+                let input = read_move_player(&local_name, &state.config.ruleset, allow_save, None)
+                    .unwrap_or(MoveInput::Quit);
+                let own = match move_or_command(state, scoreboard, input, allow_save) {
+                    Ok(mv) => mv,
+                    Err(Command::Leave) => return,
+                    Err(Command::Reprompt) => {
+                        skip_pre_round = true;
+                        continue;
+                    }
+                };
+                record_response(&state.config, scoreboard, &local_name, started);
+
+                println!("\n{}", t("network.waiting_move"));
+                let all = Move::all_for_ruleset(&state.config.ruleset);
+                let theirs = match network::exchange(own, &all) {
+                    Ok(mv) => mv,
+                    Err(e) => {
+                        handle_disconnect(state, scoreboard, &e);
+                        return;
+                    }
+                };
+                let (p1, p2) = if network::local_is_p1() {
+                    (own, theirs)
+                } else {
+                    (theirs, own)
+                };
+                record_moves(state, scoreboard, p1, p2);
 
-        let opt = read_menu_choice(1, 4);
+                countdown(&state.config);
+                let winner = decide_winner(&state.config.ruleset, p1, p2);
+                if matches!(winner, RoundWinner::Tie) && tie_is_replayed(state) {
+                    log_replayed_tie(state, p1, p2);
+                    clear_screen();
+                    print_replayed_tie(state, p1, p2);
+                    skip_pre_round = true;
+                    continue;
+                }
+                apply_round(state, p1, p2, winner);
+                log_last_round(state);
+                interrupt::track(state, scoreboard);
 
-        match opt {
-            1 => return AfterRoundAction::NextRound,
-            2 => {
                 clear_screen();
-                println!("Match history\n");
+                print_round_summary(state, p1, p2, winner);
 
-                if state.history.is_empty() {
-                    println!("No rounds played yet.");
-                } else {
-                    for r in &state.history {
-                        let winner = match r.winner {
-                            RoundWinner::Player1 => state.config.player1.as_str(),
-                            RoundWinner::Player2 => state.config.player2.as_str(),
-                            RoundWinner::Tie => "Tie",
-                        };
-                        println!(
-                            "Round {}: {} v. {}  ->  {}",
-                            r.round,
-                            r.p1_move.name(),
-                            r.p2_move.name(),
-                            winner
-                        );
+                if let Some(match_winner) = check_match_winner(state) {
+                    let action = handle_match_end(state, scoreboard, match_winner);
+                    match action {
+                        AfterMatchAction::MainMenu => return,
+                        AfterMatchAction::ContinueMatch => continue,
                     }
                 }
 
-                pause();
+                match after_round_menu(state) {
+                    AfterRoundAction::NextRound => {
+                        state.round_number += 1;
+                        skip_pre_round = true;
+                        continue;
+                    }
+                    AfterRoundAction::SaveAndMenu => {
+                        save_match(state, scoreboard);
+                        scoreboard.save();
+                        return;
+                    }
+                    AfterRoundAction::MenuNoSave => {
+                        scoreboard.save();
+                        return;
+                    }
+                    AfterRoundAction::Concede(winner) => {
+                        state.conceded_to = Some(winner);
+                        match handle_match_end(state, scoreboard, winner) {
+                            AfterMatchAction::ContinueMatch => continue,
+                            AfterMatchAction::MainMenu => return,
+                        }
+                    }
+                }
             }
-            3 => return AfterRoundAction::SaveAndMenu,
-            4 => return AfterRoundAction::MenuNoSave,
-            _ => {}
         }
     }
 }
 
-fn handle_match_end(
-    state: &mut MatchState,
-    scoreboard: &mut Scoreboard,
-    match_winner: RoundWinner,
-) -> AfterMatchAction {
-    clear_screen();
-
-    if let Some(last) = state.history.last() {
-        println!("{} chose: {}", state.config.player1, last.p1_move.name());
-        println!("{}", ascii_move(last.p1_move));
+// The other side is gone, so the match can't go on; a saved match can be
+// hosted again from "Continue".
+fn save_match(state: &mut MatchState, scoreboard: &Scoreboard) {
+    if let Some(other) = save_conflict(state) {
+        let prompt = tf(
+            "saves.overwrite",
+            &[
+                &state.save_slot.unwrap_or_default(),
+                &other.config.player1,
+                &other.config.player2,
+            ],
+        );
+        if !read_yes_no(&format!("\n{}", prompt)) {
+            state.save_slot = None;
+            save_game(state, scoreboard);
+            println!(
+                "{}",
+                tf("saves.new_slot", &[&state.save_slot.unwrap_or_default()])
+            );
+            return;
+        }
+    }
+    save_game(state, scoreboard);
+}
 
-        println!("{} chose: {}", state.config.player2, last.p2_move.name());
-        println!("{}", ascii_move(last.p2_move));
+fn handle_disconnect(state: &mut MatchState, scoreboard: &mut Scoreboard, reason: &str) {
+    println!("\n{}", lose(&tf("network.lost", &[&reason])));
+    let savable = !matches!(state.config.format, MatchFormat::SingleRound);
+    if savable && read_yes_no(&format!("\n{}", t("network.save_prompt"))) {
+        save_match(state, scoreboard);
+    } else if !savable {
+        pause();
     }
+    scoreboard.save();
+}
 
-    println!("Match complete.");
+fn commit_move(state: &mut MatchState, mv: Move) -> Option<(String, String)> {
+    if !state.config.commit_moves {
+        return None;
+    }
+    let nonce = new_nonce(&mut state.rng);
+    Some((commitment(mv, &nonce), nonce))
+}
 
-    println!(
-        "Final Score: {} {} - {} {}",
-        state.config.player1,
-        state.p1_round_wins,
-        state.p2_round_wins,
-        state.config.player2
-    );
+// Each move is checked against the hash shown before anyone saw it.
+fn reveal_commitments(
+    state: &MatchState,
+    p1: (Move, (String, String)),
+    p2: (Move, (String, String)),
+) {
+    clear_screen();
+    println!("{}\n", t("commit.title"));
+    let cfg = &state.config;
+    for (name, (mv, (hash, nonce))) in [(&cfg.player1, p1), (&cfg.player2, p2)] {
+        println!(
+            "{}",
+            tf(
+                "commit.revealed",
+                &[name, &move_name(&cfg.ruleset, mv), &nonce]
+            )
+        );
+        if verify_commitment(mv, &nonce, &hash) {
+            println!("  {}", win(&tf("commit.matches", &[&hash])));
+        } else {
+            println!("  {}", lose(&tf("commit.mismatch", &[&hash])));
+        }
+    }
+    read_line(&format!("\n{}", t("common.enter_continue")));
+}
 
-    match match_winner {
-        RoundWinner::Tie => println!("{}", yellow("It ended in a tie.")),
-        RoundWinner::Player1 => println!("{}", green(&format!("Winner: {}", state.config.player1))),
-        RoundWinner::Player2 => println!("{}", green(&format!("Winner: {}", state.config.player2))),
+fn print_sudden_death_notice(state: &MatchState) {
+    if sudden_death(state) {
+        println!("{}", notice(t("sudden.notice")));
     }
+}
 
-    let winner_name = match match_winner {
-        RoundWinner::Player1 => Some(state.config.player1.as_str()),
-        RoundWinner::Player2 => Some(state.config.player2.as_str()),
-        RoundWinner::Tie => None,
-    };
+fn print_replayed_tie(state: &MatchState, p1: Move, p2: Move) {
+    let cfg = &state.config;
 
-    scoreboard.add_match_result(
-        &state.config.player1,
-        &state.config.player2,
-        winner_name,
-        state.p1_round_wins,
-        state.p2_round_wins,
+    let rules = &cfg.ruleset;
+    println!(
+        "{}",
+        tf("match.chose", &[&cfg.player1, &move_name(rules, p1)])
     );
-    scoreboard.save();
-    clear_saved_game();
+    println!(
+        "{}",
+        tf("match.chose", &[&cfg.player2, &move_name(rules, p2)])
+    );
+    let key = if sudden_death(state) {
+        "sudden.tie"
+    } else {
+        "match.tie_replayed"
+    };
+    println!("{}", notice(t(key)));
+    read_line(&format!("\n{}", t("common.enter_continue")));
+}
+
+fn leave_event(state: &MatchState) -> &'static str {
+    if state.in_series {
+        t("match.leave_series")
+    } else if state.in_challenge {
+        t("match.leave_challenge")
+    } else {
+        t("match.leave_tournament")
+    }
+}
 
+fn after_round_menu(state: &MatchState) -> AfterRoundAction {
     loop {
-        println!("\nPost match:");
-        println!("1) Rematch (same settings)");
-        println!("2) Change ruleset / format (then rematch)");
-        if matches!(state.config.mode, Mode::SinglePlayer) {
-            println!("3) Change difficulty (then rematch)");
-            println!("4) Return to main menu");
-            let c = read_menu_choice(1, 4);
-            match c {
-                1 => {
-                    state.reset_for_rematch();
-                    return AfterMatchAction::ContinueMatch;
-                }
-                2 => {
-                    change_ruleset_and_format(&mut state.config);
-                    state.reset_for_rematch();
-                    return AfterMatchAction::ContinueMatch;
-                }
-                3 => {
-                    change_difficulty(&mut state.config);
-                    state.reset_for_rematch();
-                    return AfterMatchAction::ContinueMatch;
-                }
-                4 => return AfterMatchAction::MainMenu,
-                _ => {}
-            }
+        println!("\n{}", t("round.options"));
+        menu_item(1, t("round.next"));
+        menu_item(2, t("round.history"));
+        if state.in_event() {
+            menu_item(3, leave_event(state));
         } else {
-            println!("3) Return to main menu");
-            let c = read_menu_choice(1, 3);
-            match c {
-                1 => {
-                    state.reset_for_rematch();
-                    return AfterMatchAction::ContinueMatch;
-                }
-                2 => {
-                    change_ruleset_and_format(&mut state.config);
-                    state.reset_for_rematch();
-                    return AfterMatchAction::ContinueMatch;
+            menu_item(3, t("match.save_now"));
+            menu_item(4, t("match.menu_no_save"));
+        }
+        // Both sides would have to agree on who conceded.
+        let can_concede = state.config.mode != Mode::Network;
+        let concede = if state.in_event() { 4 } else { 5 };
+        if can_concede {
+            menu_item(concede, t("round.concede"));
+        }
+
+        let last = if can_concede { concede } else { concede - 1 };
+        let opt = read_menu_choice(1, last);
+
+        match opt {
+            1 => return AfterRoundAction::NextRound,
+            2 => view_match_history(state),
+            3 if state.in_event() => return AfterRoundAction::MenuNoSave,
+            3 => return AfterRoundAction::SaveAndMenu,
+            n if n == concede => {
+                if let Some(winner) = confirm_concede(state) {
+                    return AfterRoundAction::Concede(winner);
                 }
-                3 => return AfterMatchAction::MainMenu,
-                _ => {}
             }
+            4 if confirm_discard(state) => return AfterRoundAction::MenuNoSave,
+            _ => {}
         }
     }
 }
 
-fn change_ruleset_and_format(cfg: &mut GameConfig) {
-    clear_screen();
-
-    println!("Change ruleset:");
-    println!("1) Classic");
-    println!("2) Extended");
-    cfg.ruleset = match read_menu_choice(1, 2) {
-        1 => Ruleset::Classic,
-        _ => Ruleset::Extended,
+// Unlike leaving without saving, conceding records the match as a loss.
+// Returns the side the match goes to.
+fn confirm_concede(state: &MatchState) -> Option<RoundWinner> {
+    let cfg = &state.config;
+    // The computer never concedes.
+    let p1_concedes = cfg.mode == Mode::SinglePlayer || {
+        println!("\n{}", t("concede.who"));
+        menu_item(1, &cfg.player1);
+        menu_item(2, &cfg.player2);
+        read_menu_choice(1, 2) == 1
     };
-
-    println!("\nChange match format:");
-    println!("1) Single round");
-    println!("2) Best of N");
-    println!("3) First to K wins");
-    cfg.format = match read_menu_choice(1, 3) {
-        1 => MatchFormat::SingleRound,
-        2 => {
-            let n = loop {
-                let s = read_line("Enter N (odd number >= 1): ");
-                if let Ok(v) = s.parse::<u32>() {
-                    if v >= 1 && v % 2 == 1 {
-                        break v;
-                    }
-                }
-                println!("Invalid.");
-            };
-            MatchFormat::BestOfN(n)
-        }
-        _ => {
-            let k = loop {
-                let s = read_line("Enter K (>= 1): ");
-                if let Ok(v) = s.parse::<u32>() {
-                    if v >= 1 {
-                        break v;
-                    }
-                }
-                println!("Invalid.");
-            };
-            MatchFormat::FirstToK(k)
-        }
+    let (name, other, winner) = if p1_concedes {
+        (&cfg.player1, &cfg.player2, RoundWinner::Player2)
+    } else {
+        (&cfg.player2, &cfg.player1, RoundWinner::Player1)
     };
+    read_yes_no(&format!("\n{}", tf("concede.confirm", &[name, other]))).then_some(winner)
 }
 
+// Leaving without saving touches no save file, so an earlier save of this
+// match is still there to go back to.
+fn confirm_discard(state: &MatchState) -> bool {
+    if state.history.is_empty() {
+        return true;
+    }
+    let cfg = &state.config;
+    let (p1_score, p2_score) = displayed_score(state);
+    let summary = tf(
+        "discard.summary",
+        &[
+            &cfg.player1,
+            &p1_score,
+            &p2_score,
+            &cfg.player2,
+            &state.history.len(),
+        ],
+    );
+    println!("\n{}", summary);
+    read_yes_no(t("discard.confirm"))
+}
 
-fn change_difficulty(cfg: &mut GameConfig) {
+enum HistoryFilter {
+    All,
+    Winner(RoundWinner),
+    // true for Player 1's move, false for Player 2's.
+    Played(bool, Move),
+}
+
+impl HistoryFilter {
+    fn matches(&self, r: &RoundRecord) -> bool {
+        match *self {
+            HistoryFilter::All => true,
+            HistoryFilter::Winner(w) => r.winner == w,
+            HistoryFilter::Played(true, mv) => r.p1_move == mv,
+            HistoryFilter::Played(false, mv) => r.p2_move == mv,
+        }
+    }
+}
+
+// Read off the ruleset itself, so custom rulesets get a legend too.
+fn view_rules(ruleset: &Ruleset) {
     clear_screen();
+    println!("{}\n", tf("rules.title", &[&lang::ruleset_name(ruleset)]));
+    let table = beats_table(ruleset);
+    for mv in Move::all_for_ruleset(ruleset) {
+        let name = move_name(ruleset, mv);
+        let beaten: Vec<&str> = table[&mv].iter().map(|&b| move_name(ruleset, b)).collect();
+        if beaten.is_empty() {
+            println!("  {}", tf("editor.beats_nothing", &[&name]));
+        } else {
+            println!("  {}", tf("editor.beats", &[&name, &beaten.join(", ")]));
+        }
+    }
+    pause();
+}
 
-    println!("Change difficulty:");
-    println!("1) Easy");
-    println!("2) Normal");
-    println!("3) Hard");
-    let diff = match read_menu_choice(1, 3) {
-        1 => Difficulty::Easy,
-        2 => Difficulty::Normal,
-        _ => Difficulty::Hard,
+fn view_match_history(state: &MatchState) {
+    let cfg = &state.config;
+    let rules = &cfg.ruleset;
+    let mut filter = HistoryFilter::All;
+    loop {
+        clear_screen();
+        println!("{}\n", t("history.title"));
+
+        if state.history.is_empty() {
+            println!("{}", t("history.empty"));
+            pause();
+            return;
+        }
+
+        let shown: Vec<&RoundRecord> = state.history.iter().filter(|r| filter.matches(r)).collect();
+        for r in &shown {
+            let winner = match r.winner {
+                RoundWinner::Player1 => cfg.player1.as_str(),
+                RoundWinner::Player2 => cfg.player2.as_str(),
+                RoundWinner::Tie => t("history.tie"),
+            };
+            let row = tf(
+                "history.row",
+                &[
+                    &r.round,
+                    &move_name(rules, r.p1_move),
+                    &move_name(rules, r.p2_move),
+                    &winner,
+                ],
+            );
+            let row = if r.tiebreaker {
+                format!("{}  ({})", row, t("history.tiebreaker"))
+            } else {
+                row
+            };
+            // Always from player 1's side, whoever is looking.
+            let row = match r.winner {
+                RoundWinner::Player1 => win(&row),
+                RoundWinner::Player2 => lose(&row),
+                RoundWinner::Tie => tie(&row),
+            };
+            println!("{}", row);
+        }
+        if shown.is_empty() {
+            println!("{}", t("history.no_match"));
+        }
+        println!(
+            "\n{}",
+            tf("history.count", &[&shown.len(), &state.history.len()])
+        );
+
+        println!("\n{}", t("history.filter"));
+        menu_item(1, t("history.all"));
+        menu_item(2, tf("history.won_by", &[&cfg.player1]));
+        menu_item(3, tf("history.won_by", &[&cfg.player2]));
+        menu_item(4, t("history.ties"));
+        menu_item(5, tf("history.played_by", &[&cfg.player1]));
+        menu_item(6, tf("history.played_by", &[&cfg.player2]));
+        menu_item(7, t("common.back"));
+
+        filter = match read_menu_choice(1, 7) {
+            1 => HistoryFilter::All,
+            2 => HistoryFilter::Winner(RoundWinner::Player1),
+            3 => HistoryFilter::Winner(RoundWinner::Player2),
+            4 => HistoryFilter::Winner(RoundWinner::Tie),
+            c @ (5 | 6) => {
+                let all = Move::all_for_ruleset(rules);
+                println!("\n{}", t("history.pick_move"));
+                for (i, &mv) in all.iter().enumerate() {
+                    menu_item(i + 1, move_name(rules, mv));
+                }
+                let picked = all[read_menu_choice(1, all.len() as i32) as usize - 1];
+                HistoryFilter::Played(c == 5, picked)
+            }
+            _ => return,
+        };
+    }
+}
+
+// Repeated until someone wins it. None if a player quits, which leaves the
+// match drawn.
+fn play_tiebreaker(state: &mut MatchState, scoreboard: &mut Scoreboard) -> Option<RoundWinner> {
+    loop {
+        let p1 = tiebreaker_move(state, true)?;
+        let p2 = match state.config.mode {
+            Mode::SinglePlayer => ai_move(state, p1).chosen,
+            Mode::Multiplayer | Mode::Network => tiebreaker_move(state, false)?,
+        };
+        record_moves(state, scoreboard, p1, p2);
+
+        countdown(&state.config);
+        let winner = decide_winner(&state.config.ruleset, p1, p2);
+        state.round_number += 1;
+        apply_tiebreaker(state, p1, p2, winner);
+        log_last_round(state);
+
+        clear_screen();
+        let cfg = &state.config;
+        println!(
+            "{}",
+            tf("match.chose", &[&cfg.player1, &move_name(&cfg.ruleset, p1)])
+        );
+        print_move_art(cfg, p1);
+        println!(
+            "{}",
+            tf("match.chose", &[&cfg.player2, &move_name(&cfg.ruleset, p2)])
+        );
+        print_move_art(cfg, p2);
+        if winner != RoundWinner::Tie {
+            return Some(winner);
+        }
+        println!("{}", notice(t("tiebreak.again")));
+        pause();
+    }
+}
+
+// Hidden when two people share the screen.
+fn tiebreaker_move(state: &MatchState, player1: bool) -> Option<Move> {
+    let cfg = &state.config;
+    let name = if player1 { &cfg.player1 } else { &cfg.player2 };
+    let hidden = cfg.mode == Mode::Multiplayer;
+    loop {
+        clear_screen();
+        println!("{}", t("tiebreak.title"));
+        if hidden {
+            println!("{}", tf("match.turn", &[name]));
+        }
+        println!(
+            "{}",
+            tf("match.accepted", &[&accepted_inputs_line(&cfg.ruleset)])
+        );
+        println!("{}", t("match.command_hint"));
+        let input = if hidden {
+            read_move_hidden("", &cfg.ruleset, false)
+        } else {
+            read_move_player(name, &cfg.ruleset, false, None)?
+        };
+        match input {
+            MoveInput::Move(mv) => {
+                if hidden && player1 {
+                    clear_screen();
+                    println!("{}", tf("match.locked", &[name]));
+                    println!("\n{}", tf("match.pass_to", &[&cfg.player2]));
+                    let _ = input::line();
+                }
+                return Some(mv);
+            }
+            MoveInput::History => view_match_history(state),
+            MoveInput::Rules => view_rules(&cfg.ruleset),
+            MoveInput::Quit | MoveInput::Save => return None,
+        }
+    }
+}
+
+// Saving is handled here so every prompt leaves the match the same way.
+fn move_or_command(
+    state: &mut MatchState,
+    scoreboard: &mut Scoreboard,
+    input: MoveInput,
+    allow_save: bool,
+) -> Result<Move, Command> {
+    let action = match input {
+        MoveInput::Move(mv) => return Ok(mv),
+        MoveInput::History => {
+            view_match_history(state);
+            return Err(Command::Reprompt);
+        }
+        MoveInput::Rules => {
+            view_rules(&state.config.ruleset);
+            return Err(Command::Reprompt);
+        }
+        MoveInput::Save => AfterRoundAction::SaveAndMenu,
+        MoveInput::Quit => quit_menu(state, allow_save),
     };
-    cfg.difficulty = Some(diff);
+    match action {
+        AfterRoundAction::NextRound => return Err(Command::Reprompt),
+        AfterRoundAction::SaveAndMenu => save_match(state, scoreboard),
+        AfterRoundAction::MenuNoSave | AfterRoundAction::Concede(_) => {}
+    }
+    scoreboard.save();
+    Err(Command::Leave)
 }
 
-fn print_match_header(state: &MatchState) {
+// NextRound here means going back to the same prompt.
+fn quit_menu(state: &MatchState, allow_save: bool) -> AfterRoundAction {
+    println!("\n{}", t("quit.title"));
+    menu_item(1, t("quit.keep_playing"));
+    if allow_save {
+        menu_item(2, t("match.save_now"));
+        menu_item(3, t("match.menu_no_save"));
+        return match read_menu_choice(1, 3) {
+            2 => AfterRoundAction::SaveAndMenu,
+            3 if confirm_discard(state) => AfterRoundAction::MenuNoSave,
+            _ => AfterRoundAction::NextRound,
+        };
+    }
+    let leave = if state.in_event() {
+        leave_event(state)
+    } else {
+        t("match.menu")
+    };
+    menu_item(2, leave);
+    match read_menu_choice(1, 2) {
+        1 => AfterRoundAction::NextRound,
+        _ => AfterRoundAction::MenuNoSave,
+    }
+}
+
+fn handle_match_end(
+    state: &mut MatchState,
+    scoreboard: &mut Scoreboard,
+    mut match_winner: RoundWinner,
+) -> AfterMatchAction {
+    // A tiebreaker adds a round win, after which the format may look decided.
+    let capped = ended_by_round_limit(state);
+    // Network play would need both sides to agree, and tournaments have
+    // their own tiebreak.
+    let can_break_tie = state.config.mode != Mode::Network && !state.in_tournament;
+    if match_winner == RoundWinner::Tie && can_break_tie {
+        println!("\n{}", t("victory.tie"));
+        if read_yes_no(&format!("\n{}", t("tiebreak.offer"))) {
+            if let Some(winner) = play_tiebreaker(state, scoreboard) {
+                match_winner = winner;
+            }
+        }
+    }
+
+    state.ended_at = Some(unix_now());
+    let unlocked = if state.config.ranked {
+        scoreboard.add_match_result(state, match_winner)
+    } else {
+        vec![]
+    };
+    scoreboard.save();
+
+    clear_screen();
+    show_victory(state, match_winner);
+    if capped {
+        let max = state.config.max_rounds.unwrap_or_default();
+        println!("{}", tf("victory.round_limit", &[&max]));
+    }
+    for (name, achievement) in &unlocked {
+        let line = tf(
+            "victory.achievement",
+            &[name, &lang::achievement_name(*achievement)],
+        );
+        println!("{}", notice(&line));
+    }
+    print_move_histogram(state);
+
+    clear_saved_game(state.save_slot.take());
+    interrupt::clear();
+
+    if state.in_event() {
+        read_line(&format!("\n{}", t("common.enter_continue")));
+        return AfterMatchAction::MainMenu;
+    }
+
+    loop {
+        println!("\n{}", t("post.title"));
+        menu_item(1, t("post.rematch"));
+        if state.config.mode != Mode::Network {
+            menu_item(2, t("post.change_rules"));
+        }
+        if matches!(state.config.mode, Mode::SinglePlayer) {
+            menu_item(3, t("post.change_difficulty"));
+            menu_item(4, t("post.export"));
+            menu_item(5, t("match.menu"));
+            let c = read_menu_choice(1, 5);
+            match c {
+                1 => {
+                    single_player_rematch(state);
+                    return AfterMatchAction::ContinueMatch;
+                }
+                2 => {
+                    change_ruleset_and_format(&mut state.config);
+                    single_player_rematch(state);
+                    return AfterMatchAction::ContinueMatch;
+                }
+                3 => {
+                    change_difficulty(&mut state.config);
+                    single_player_rematch(state);
+                    return AfterMatchAction::ContinueMatch;
+                }
+                4 => export_match_csv(state),
+                5 => return AfterMatchAction::MainMenu,
+                _ => {}
+            }
+        } else if state.config.mode == Mode::Network {
+            // Changing the rules on one side only would split the match in
+            // two, so a network rematch keeps them.
+            menu_item(2, t("post.export"));
+            menu_item(3, t("match.menu"));
+            match read_menu_choice(1, 3) {
+                1 => {
+                    state.reset_for_rematch();
+                    return AfterMatchAction::ContinueMatch;
+                }
+                2 => export_match_csv(state),
+                _ => return AfterMatchAction::MainMenu,
+            }
+        } else {
+            menu_item(3, t("post.export"));
+            menu_item(4, t("match.menu"));
+            let c = read_menu_choice(1, 4);
+            match c {
+                1 => {
+                    choose_sides(state);
+                    state.reset_for_rematch();
+                    return AfterMatchAction::ContinueMatch;
+                }
+                2 => {
+                    change_ruleset_and_format(&mut state.config);
+                    choose_sides(state);
+                    state.reset_for_rematch();
+                    return AfterMatchAction::ContinueMatch;
+                }
+                3 => export_match_csv(state),
+                4 => return AfterMatchAction::MainMenu,
+                _ => {}
+            }
+        }
+    }
+}
+
+// The computer can keep what it learned about the human's moves, minus any
+// the new ruleset doesn't have.
+fn single_player_rematch(state: &mut MatchState) {
+    let keep = read_yes_no(&format!("\n{}", t("post.keep_memory")));
+    let mut recent = std::mem::take(&mut state.human_recent);
+    state.reset_for_rematch();
+    if keep {
+        let moves = Move::all_for_ruleset(&state.config.ruleset);
+        recent.retain(|m| moves.contains(m));
+        state.human_recent = recent;
+    }
+}
+
+fn choose_sides(state: &mut MatchState) {
+    println!("\n{}", t("sides.title"));
+    menu_item(1, t("sides.keep"));
+    menu_item(2, t("sides.swap"));
+    menu_item(3, t("sides.random"));
+    let swap = match read_menu_choice(1, 3) {
+        2 => true,
+        3 => state.rng.gen_bool(0.5),
+        _ => false,
+    };
+    if swap {
+        state.swap_sides();
+    }
+    println!("{}", tf("sides.first", &[&state.config.player1]));
+}
+
+fn export_match_csv(state: &MatchState) {
     let cfg = &state.config;
+    let stamp = unix_now();
+    let path = format!(
+        "rps_{}_vs_{}_{}.csv",
+        file_safe(&cfg.player1),
+        file_safe(&cfg.player2),
+        stamp
+    );
+
+    let mut csv = String::from("round,p1_move,p2_move,winner,cumulative_p1,cumulative_p2\n");
+    let (mut p1_total, mut p2_total) = (0, 0);
+    for r in &state.history {
+        let winner = match r.winner {
+            RoundWinner::Player1 => {
+                p1_total += 1;
+                cfg.player1.as_str()
+            }
+            RoundWinner::Player2 => {
+                p2_total += 1;
+                cfg.player2.as_str()
+            }
+            RoundWinner::Tie => "Tie",
+        };
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            r.round,
+            csv_field(cfg.ruleset.move_name(r.p1_move)),
+            csv_field(cfg.ruleset.move_name(r.p2_move)),
+            csv_field(winner),
+            p1_total,
+            p2_total
+        ));
+    }
+
+    match fs::write(&path, csv) {
+        Ok(()) => println!("\n{}", tf("post.exported", &[&path])),
+        Err(e) => println!("\n{}", tf("common.write_failed", &[&path, &e])),
+    }
+}
+
+fn file_safe(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn change_ruleset_and_format(cfg: &mut GameConfig) {
+    clear_screen();
+
+    println!("{}", t("change.ruleset"));
+    cfg.ruleset = read_ruleset(None);
 
-    let ruleset = match cfg.ruleset {
-        Ruleset::Classic => "Classic",
-        Ruleset::Extended => "Extended",
+    println!("\n{}", t("change.format"));
+    cfg.format = read_format(None);
+    // A handicap that no longer fits the format would decide the match.
+    if !valid_handicap(&cfg.format, cfg.handicap) {
+        cfg.handicap = (0, 0);
+    }
+}
+
+fn read_format(default: Option<MatchFormat>) -> MatchFormat {
+    menu_item(1, t("format.single"));
+    menu_item(2, t("format.best_of_n"));
+    menu_item(3, t("format.first_to_k"));
+    menu_item(4, t("format.win_by_2"));
+    menu_item(5, t("format.points"));
+    if let Some(d) = default {
+        print_default(&format_name(d));
+    }
+    let Some(choice) = read_menu_choice_or(1, 5, default.is_some()) else {
+        return default.unwrap_or(MatchFormat::SingleRound);
     };
+    match choice {
+        1 => MatchFormat::SingleRound,
+        2 => {
+            let n = loop {
+                let s = read_line(t("format.enter_n"));
+                if let Ok(v) = s.parse::<u32>() {
+                    if v >= 1 {
+                        break v;
+                    }
+                }
+                println!("{}", t("common.invalid"));
+            };
+            MatchFormat::BestOfN(n)
+        }
+        3 | 4 => {
+            let k = loop {
+                let s = read_line(t("format.enter_k"));
+                if let Ok(v) = s.parse::<u32>() {
+                    if v >= 1 {
+                        break v;
+                    }
+                }
+                println!("{}", t("common.invalid"));
+            };
+            if choice == 3 {
+                MatchFormat::FirstToK(k)
+            } else {
+                MatchFormat::FirstToKWinBy2(k)
+            }
+        }
+        _ => {
+            let target = loop {
+                let s = read_line(t("format.enter_points"));
+                if let Ok(v) = s.parse::<u32>() {
+                    if v >= 1 {
+                        break v;
+                    }
+                }
+                println!("{}", t("common.invalid"));
+            };
+            MatchFormat::Points { target }
+        }
+    }
+}
+
+// A remembered custom ruleset is kept as it was, even if its file changed.
+fn read_ruleset(default: Option<&Ruleset>) -> Ruleset {
+    loop {
+        menu_item(1, t("ruleset.classic"));
+        menu_item(2, t("ruleset.extended"));
+        menu_item(3, t("ruleset.custom"));
+        menu_item(4, tf("ruleset.saved", &[&RULESET_DIR]));
+        menu_item(5, t("ruleset.generalized"));
+        if let Some(d) = default {
+            print_default(lang::ruleset_name(d));
+        }
+        let Some(choice) = read_menu_choice_or(1, 5, default.is_some()) else {
+            return default.cloned().unwrap_or(Ruleset::Classic);
+        };
+        match choice {
+            1 => return Ruleset::Classic,
+            2 => {
+                let ruleset = Ruleset::Extended;
+                if let Err(e) = check_ruleset(&ruleset) {
+                    println!("{}\n", e.describe(&ruleset));
+                    continue;
+                }
+                return ruleset;
+            }
+            5 => {
+                let n = read_line(t("ruleset.generalized_size"))
+                    .parse()
+                    .unwrap_or(0);
+                let names = read_line(t("ruleset.generalized_names"));
+                let names = (!names.is_empty())
+                    .then(|| names.split(',').map(|s| s.trim().to_string()).collect());
+                match CustomRuleset::generalized(n, names) {
+                    Ok(rules) => return Ruleset::Custom(rules),
+                    Err(e) => println!("{}\n", e),
+                }
+            }
+            choice => {
+                let path = if choice == 3 {
+                    read_line(t("ruleset.file"))
+                } else {
+                    let files = list_ruleset_files();
+                    if files.is_empty() {
+                        println!("{}\n", t("ruleset.none_saved"));
+                        continue;
+                    }
+                    for (i, f) in files.iter().enumerate() {
+                        menu_item(i + 1, f);
+                    }
+                    files[read_menu_choice(1, files.len() as i32) as usize - 1].clone()
+                };
+                let ruleset = match CustomRuleset::load(&path) {
+                    Ok(rules) => Ruleset::Custom(rules),
+                    Err(e) => {
+                        println!("{}\n", e);
+                        continue;
+                    }
+                };
+                match check_ruleset(&ruleset) {
+                    Ok(()) => return ruleset,
+                    Err(e) => println!("{}\n", e.describe(&ruleset)),
+                }
+            }
+        }
+    }
+}
+
+fn change_difficulty(cfg: &mut GameConfig) {
+    clear_screen();
+
+    println!("{}", t("change.difficulty"));
+    let difficulty = read_difficulty(None);
+    cfg.difficulty = Some(difficulty);
+    if cfg.persona.is_some() {
+        let persona = Persona::for_difficulty(difficulty);
+        cfg.persona = Some(persona);
+        cfg.player2 = persona.name().to_string();
+    } else {
+        cfg.player2 = computer_name(difficulty, cfg.reveal_difficulty);
+    }
+}
+
+fn read_difficulty(default: Option<Difficulty>) -> Difficulty {
+    let levels = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Expert,
+        Difficulty::Adaptive,
+        Difficulty::Mirror,
+    ];
+    for (i, &d) in levels.iter().enumerate() {
+        let name = lang::difficulty_name(d);
+        match d {
+            Difficulty::Adaptive => menu_item(
+                i + 1,
+                format!("{} ({})", name, t("difficulty.adaptive_hint")),
+            ),
+            Difficulty::Mirror => {
+                menu_item(i + 1, format!("{} ({})", name, t("difficulty.mirror_hint")))
+            }
+            _ => menu_item(i + 1, name),
+        }
+    }
+    if let Some(d) = default {
+        print_default(lang::difficulty_name(d));
+    }
+    match read_menu_choice_or(1, levels.len() as i32, default.is_some()) {
+        Some(n) => levels[n as usize - 1],
+        None => default.unwrap_or(Difficulty::Easy),
+    }
+}
 
-    let fmt_line = match cfg.format {
-        MatchFormat::SingleRound => "Single round".to_string(),
-        MatchFormat::BestOfN(n) => format!("Best of {}", n),
-        MatchFormat::FirstToK(k) => format!("First to {} wins", k),
+fn format_duration(secs: u64) -> String {
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if h > 0 {
+        format!("{}h {:02}m {:02}s", h, m, s)
+    } else if m > 0 {
+        format!("{}m {:02}s", m, s)
+    } else {
+        format!("{}s", s)
+    }
+}
+
+fn format_secs(d: Duration) -> String {
+    format!("{:.1}s", d.as_secs_f64())
+}
+
+fn format_name(format: MatchFormat) -> String {
+    match format {
+        MatchFormat::SingleRound => t("format.single").to_string(),
+        MatchFormat::BestOfN(n) => tf("header.best_of", &[&n]),
+        MatchFormat::FirstToK(k) => tf("header.first_to", &[&k]),
+        MatchFormat::FirstToKWinBy2(k) => tf("header.win_by_2", &[&k]),
+        MatchFormat::Points { target } => tf("header.first_to_points", &[&target]),
+    }
+}
+
+// The computer isn't a fair coin, so single-player shows how the rounds
+// have actually gone instead of odds. None once the match is decided.
+fn match_point_notice(state: &MatchState) -> Option<String> {
+    let cfg = &state.config;
+    match match_point(state) {
+        (true, true) => Some(t("header.double_match_point").to_string()),
+        (true, false) => Some(tf("header.match_point", &[&cfg.player1])),
+        (false, true) => Some(tf("header.match_point", &[&cfg.player2])),
+        (false, false) => None,
+    }
+}
+
+fn match_outlook(state: &MatchState) -> Option<String> {
+    let cfg = &state.config;
+    if matches!(cfg.format, MatchFormat::SingleRound) || check_match_winner(state).is_some() {
+        return None;
+    }
+    let pct = |p: f64| format!("{:.0}", p * 100.0);
+    let (key, p1, p2) = match cfg.mode {
+        Mode::SinglePlayer => {
+            let (won, lost, _) = observed_round_odds(&state.history)?;
+            ("outlook.observed", won, lost)
+        }
+        _ => {
+            let odds = random_round_odds(&cfg.ruleset);
+            let (p1, p2) = win_probability(cfg.format, displayed_score(state), odds);
+            ("outlook.random", p1, p2)
+        }
     };
+    Some(tf(key, &[&cfg.player1, &pct(p1), &cfg.player2, &pct(p2)]))
+}
+
+fn print_match_header(state: &MatchState) {
+    let cfg = &state.config;
 
-    let diff_line = match cfg.difficulty {
-        Some(Difficulty::Easy) => Some("Easy"),
-        Some(Difficulty::Normal) => Some("Normal"),
-        Some(Difficulty::Hard) => Some("Hard"),
-        None => None,
+    let fmt_line = format_name(cfg.format);
+
+    // Labels are padded to a fixed column so translations still line up.
+    let field = |key: &'static str, value: &dyn std::fmt::Display| {
+        println!("{:<16}{}", t(key), value);
     };
 
-    println!("==============================");
-    println!("        Match Details");
-    println!("==============================\n");
+    print_heading(t("header.title"));
+    if !cfg.ranked {
+        println!("{}\n", notice(t("header.practice")));
+    }
 
-    println!("Players:        {} v. {}", cfg.player1, cfg.player2);
-    println!("Ruleset:        {}", ruleset);
-    println!("Format:         {}", fmt_line);
-    if let Some(d) = diff_line {
-        println!("Difficulty:     {}", d);
+    field(
+        "header.players",
+        &format!("{} v. {}", cfg.player1, cfg.player2),
+    );
+    field("header.ruleset", &lang::ruleset_name(&cfg.ruleset));
+    field("header.format", &fmt_line);
+    if let Some(d) = cfg.difficulty.filter(|_| cfg.reveal_difficulty) {
+        field("header.difficulty", &lang::difficulty_name(d));
+    }
+    if let Some(secs) = cfg.move_timeout_secs {
+        field("header.timer", &format!("{}s", secs));
+    }
+    if cfg.handicap != (0, 0) {
+        field(
+            "header.handicap",
+            &format!("{}-{}", cfg.handicap.0, cfg.handicap.1),
+        );
     }
 
     if state.round_number > 1 || state.p1_round_wins > 0 || state.p2_round_wins > 0 {
-        println!("\n------------------------------");
-        println!("SCORE");
-        println!("------------------------------");
+        let rule = layout::rule('-', HEADING_WIDTH);
+        println!("\n{}", rule);
+        println!("{}", t("header.score"));
+        println!("{}", rule);
 
+        let (p1_score, p2_score) = displayed_score(state);
         let score_line = format!(
             "{} {} - {} {}",
-            cfg.player1, state.p1_round_wins, state.p2_round_wins, cfg.player2
+            cfg.player1, p1_score, p2_score, cfg.player2
         );
-        println!("{}", cyan(&score_line));
+        println!("{}", accent(&score_line));
 
         match cfg.format {
             MatchFormat::SingleRound => {}
-            MatchFormat::BestOfN(n) => {
-                let needed = n / 2 + 1;
-                let p1_left = needed.saturating_sub(state.p1_round_wins);
-                let p2_left = needed.saturating_sub(state.p2_round_wins);
-                println!("Wins needed:    {}", needed);
-                println!(
-                    "Wins to go:     {} needs {} | {} needs {}",
-                    cfg.player1, p1_left, cfg.player2, p2_left
+            MatchFormat::BestOfN(_) | MatchFormat::FirstToK(_) | MatchFormat::FirstToKWinBy2(_) => {
+                let needed = rounds_needed(&cfg.format).unwrap_or_default();
+                let (p1_left, p2_left) = wins_to_go(state).unwrap_or_default();
+                let label = match cfg.format {
+                    MatchFormat::BestOfN(_) => "header.wins_needed",
+                    _ => "header.target_wins",
+                };
+                field(label, &needed);
+                field(
+                    "header.wins_to_go",
+                    &tf(
+                        "header.needs",
+                        &[&cfg.player1, &p1_left, &cfg.player2, &p2_left],
+                    ),
                 );
             }
-            MatchFormat::FirstToK(k) => {
-                let p1_left = k.saturating_sub(state.p1_round_wins);
-                let p2_left = k.saturating_sub(state.p2_round_wins);
-                println!("Target wins:    {}", k);
-                println!(
-                    "Wins to go:     {} needs {} | {} needs {}",
-                    cfg.player1, p1_left, cfg.player2, p2_left
+            MatchFormat::Points { target } => {
+                let p1_left = target.saturating_sub(p1_score);
+                let p2_left = target.saturating_sub(p2_score);
+                field("header.target_points", &target);
+                field(
+                    "header.points_to_go",
+                    &tf(
+                        "header.needs",
+                        &[&cfg.player1, &p1_left, &cfg.player2, &p2_left],
+                    ),
                 );
             }
         }
 
+        if let Some(notice) = match_point_notice(state) {
+            println!("{}", color::notice(&notice));
+        }
+
         if !matches!(cfg.format, MatchFormat::SingleRound) {
-            println!("Round:          {}", state.round_number);
+            if let Some(outlook) = match_outlook(state) {
+                field("header.outlook", &outlook);
+            }
+            match cfg.max_rounds {
+                Some(max) => field("header.round", &format!("{} / {}", state.round_number, max)),
+                None => field("header.round", &state.round_number),
+            }
+            if !state.history.is_empty() {
+                field("header.trend", &history_sparkline(&state.history));
+            }
         }
 
         println!();
     }
 }
 
+// Emoji falls back to the ASCII art when the terminal can't show them.
+fn print_move_art(cfg: &GameConfig, mv: Move) {
+    if cfg.show_emoji && unicode_supported() {
+        if let Some(emoji) = emoji_move(mv) {
+            println!("    {}\n", emoji);
+        }
+    } else if cfg.show_ascii || cfg.show_emoji {
+        println!("{}", ascii_move(mv));
+    }
+}
+
+// Both moves' ASCII art next to each other. False, with nothing printed, when
+// the art isn't shown, a custom move has none or the terminal is too narrow.
+fn print_showdown(cfg: &GameConfig, p1: Move, p2: Move) -> bool {
+    let ascii = (cfg.show_ascii || cfg.show_emoji) && !(cfg.show_emoji && unicode_supported());
+    let (art1, art2) = (ascii_move(p1), ascii_move(p2));
+    if !ascii || art1.is_empty() || art2.is_empty() {
+        return false;
+    }
+    let column = |name: &str, mv: Move, art: &str| {
+        let chose = tf("match.chose", &[&name, &move_name(&cfg.ruleset, mv)]);
+        format!("{}\n{}", chose, art.trim_end_matches('\n'))
+    };
+    let left = column(&cfg.player1, p1, art1);
+    let right = column(&cfg.player2, p2, art2);
+    match layout::side_by_side(&left, &right, t("round.vs")) {
+        Some(block) => {
+            println!("{}\n", block);
+            true
+        }
+        None => false,
+    }
+}
+
+fn graphics_enabled(cfg: &GameConfig) -> bool {
+    cfg.show_ascii || cfg.show_emoji
+}
+
+// Bad art files are worth a warning but not worth stopping over.
+fn load_move_art(defaults: &Defaults) {
+    let dir = defaults.art_dir.as_deref().unwrap_or(Path::new(ART_DIR));
+    let problems = load_art(dir);
+    for (path, problem) in &problems {
+        let path = path.display();
+        let warning = match problem {
+            ArtError::Empty => tf("warn.art_empty", &[&path]),
+            ArtError::NotAscii => tf("warn.art_not_ascii", &[&path]),
+            ArtError::Read(e) => tf("warn.art_unreadable", &[&path, e]),
+        };
+        println!("{}", notice(&warning));
+    }
+    if !problems.is_empty() {
+        pause();
+    }
+}
+
+// Player 1's results, oldest first: a full bar for a win, a low one for a
+// loss and a half one for a tie, or + - = without Unicode. Only the latest
+// rounds fit.
+fn history_sparkline(history: &[RoundRecord]) -> String {
+    let bars = if unicode_supported() {
+        ['█', '▁', '▄']
+    } else {
+        ['+', '-', '=']
+    };
+    let skip = history.len().saturating_sub(SPARKLINE_ROUNDS);
+    history[skip..]
+        .iter()
+        .map(|r| match r.winner {
+            RoundWinner::Player1 => bars[0],
+            RoundWinner::Player2 => bars[1],
+            RoundWinner::Tie => bars[2],
+        })
+        .collect()
+}
+
+// The first of these that's set decides, the same way the C library
+// picks the character set.
+fn unicode_supported() -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+        .map(|locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+        .unwrap_or(false)
+}
+
+fn countdown(cfg: &GameConfig) {
+    if !graphics_enabled(cfg) || !animations_enabled() {
+        return;
+    }
+
+    let delay = Duration::from_millis(animation_delay_ms());
+    println!();
+    for key in [
+        "countdown.rock",
+        "countdown.paper",
+        "countdown.scissors",
+        "countdown.shoot",
+    ] {
+        println!("{}", t(key));
+        thread::sleep(delay);
+    }
+}
+
+// Never animate into a pipe or file, so scripted runs don't stall.
+fn animations_enabled() -> bool {
+    io::stdout().is_terminal() && !std::env::args().any(|a| a == "--no-animation")
+}
+
+// From the settings menu; RPS_ANIMATION_MS still wins over it.
+static ANIMATION_MS: Mutex<Option<u64>> = Mutex::new(None);
+
+fn set_animation_delay(ms: Option<u64>) {
+    *ANIMATION_MS.lock().unwrap_or_else(|e| e.into_inner()) = ms;
+}
+
+fn animation_delay_ms() -> u64 {
+    std::env::var(ANIMATION_ENV)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .or(*ANIMATION_MS.lock().unwrap_or_else(|e| e.into_inner()))
+        .unwrap_or(DEFAULT_ANIMATION_MS)
+}
+
+// Started before a person is asked for a move; None when the answer will
+// come from a script, which says nothing about anyone's reactions.
+fn response_timer() -> Option<Instant> {
+    (!input::script_pending()).then(Instant::now)
+}
+
+fn record_response(
+    cfg: &GameConfig,
+    scoreboard: &mut Scoreboard,
+    name: &str,
+    started: Option<Instant>,
+) {
+    if let Some(started) = started.filter(|_| cfg.ranked) {
+        scoreboard.record_response(name, started.elapsed());
+    }
+}
+
+// The computer's moves aren't counted, and practice matches count nothing.
+fn record_moves(state: &MatchState, scoreboard: &mut Scoreboard, p1: Move, p2: Move) {
+    if !state.config.ranked {
+        return;
+    }
+    scoreboard.record_move(&state.config.player1, p1);
+    if state.config.mode != Mode::SinglePlayer {
+        scoreboard.record_move(&state.config.player2, p2);
+    }
+}
+
+// The log is best-effort. The first failure is reported, and after that
+// play carries on quietly whether or not the writes work.
+fn log_last_round(state: &MatchState) {
+    if let Some(record) = state.history.last() {
+        write_event_log(state, record, false);
+    }
+}
+
+fn log_replayed_tie(state: &MatchState, p1: Move, p2: Move) {
+    let record = RoundRecord {
+        round: state.round_number,
+        p1_move: p1,
+        p2_move: p2,
+        winner: RoundWinner::Tie,
+        tiebreaker: false,
+    };
+    write_event_log(state, &record, true);
+}
+
+fn write_event_log(state: &MatchState, record: &RoundRecord, replayed: bool) {
+    let Some(path) = &state.config.event_log else {
+        return;
+    };
+    if let Err(e) = log_round(path, record, replayed) {
+        static WARNED: Once = Once::new();
+        WARNED.call_once(|| {
+            println!("{}", notice(&tf("warn.event_log", &[&path.display(), &e])));
+            pause();
+        });
+    }
+}
+
+fn ai_hint(cfg: &GameConfig, decision: &AiDecision) -> Option<String> {
+    if !cfg.show_ai_hints || !matches!(cfg.difficulty, Some(Difficulty::Hard)) {
+        return None;
+    }
+    let predicted = decision.predicted?;
+    let name = move_name(&cfg.ruleset, predicted);
+    let reason = match decision.rationale.as_ref()? {
+        Rationale::Cycle { period } => tf("hint.cycle", &[&name, period]),
+        Rationale::Frequency { count, out_of } => tf("hint.frequency", &[&name, count, out_of]),
+    };
+    let chosen = move_name(&cfg.ruleset, decision.chosen);
+    // The counter gives way when it just lost; see choose_ai_move.
+    let action = if beats(&cfg.ruleset, decision.chosen, predicted) {
+        tf("hint.countered", &[&chosen])
+    } else {
+        tf("hint.switched", &[&chosen])
+    };
+    Some(format!("{} {}", reason, action))
+}
+
 fn print_round_summary(state: &MatchState, p1: Move, p2: Move, winner: RoundWinner) {
     let cfg = &state.config;
 
-    println!("{} chose: {}", cfg.player1, p1.name());
-    println!("{}", ascii_move(p1));
+    if !print_showdown(cfg, p1, p2) {
+        println!(
+            "{}",
+            tf("match.chose", &[&cfg.player1, &move_name(&cfg.ruleset, p1)])
+        );
+        print_move_art(cfg, p1);
 
-    println!("{} chose: {}", cfg.player2, p2.name());
-    println!("{}", ascii_move(p2));
+        println!(
+            "{}",
+            tf("match.chose", &[&cfg.player2, &move_name(&cfg.ruleset, p2)])
+        );
+        print_move_art(cfg, p2);
+    }
 
     let round_winner_line = match winner {
-        RoundWinner::Tie => yellow("Round winner: Nobody (it's a tie)"),
-        RoundWinner::Player1 => green(&format!("Round winner: {}", cfg.player1)),
-        RoundWinner::Player2 => green(&format!("Round winner: {}", cfg.player2)),
+        RoundWinner::Tie => result_line(cfg, winner, t("round.winner_tie")),
+        RoundWinner::Player1 => result_line(cfg, winner, &tf("round.winner", &[&cfg.player1])),
+        RoundWinner::Player2 => result_line(cfg, winner, &tf("round.winner", &[&cfg.player2])),
     };
 
     println!("{}", round_winner_line);
 
+    // A tie ends the run, so it only counts back over decisive rounds.
+    let run = state
+        .history
+        .iter()
+        .rev()
+        .take_while(|r| r.winner == winner)
+        .count();
+    let streaker = match winner {
+        RoundWinner::Player1 => Some(&cfg.player1),
+        RoundWinner::Player2 => Some(&cfg.player2),
+        RoundWinner::Tie => None,
+    };
+    if let Some(name) = streaker.filter(|_| run >= 2) {
+        println!("{}", tf("round.in_a_row", &[name, &run]));
+    }
+
+    let (p1_score, p2_score) = displayed_score(state);
     println!(
-        "Current Score: {} {} - {} {}",
-        cfg.player1, state.p1_round_wins, state.p2_round_wins, cfg.player2
+        "{}",
+        tf(
+            "round.score",
+            &[&cfg.player1, &p1_score, &p2_score, &cfg.player2]
+        )
     );
 
     match cfg.format {
-        MatchFormat::BestOfN(n) => {
-            let needed = n / 2 + 1;
-            let p1_left = needed.saturating_sub(state.p1_round_wins);
-            let p2_left = needed.saturating_sub(state.p2_round_wins);
-            println!("Wins to go: {} {}, {} {}", cfg.player1, p1_left, cfg.player2, p2_left);
+        MatchFormat::BestOfN(_) | MatchFormat::FirstToK(_) | MatchFormat::FirstToKWinBy2(_) => {
+            let (p1_left, p2_left) = wins_to_go(state).unwrap_or_default();
+            println!(
+                "{}",
+                tf(
+                    "round.wins_to_go",
+                    &[&cfg.player1, &p1_left, &cfg.player2, &p2_left]
+                )
+            );
         }
-        MatchFormat::FirstToK(k) => {
-            let p1_left = k.saturating_sub(state.p1_round_wins);
-            let p2_left = k.saturating_sub(state.p2_round_wins);
-            println!("Wins to go: {} {}, {} {}", cfg.player1, p1_left, cfg.player2, p2_left);
+        MatchFormat::Points { target } => {
+            let p1_left = target.saturating_sub(p1_score);
+            let p2_left = target.saturating_sub(p2_score);
+            println!(
+                "{}",
+                tf(
+                    "round.points_to_go",
+                    &[&cfg.player1, &p1_left, &cfg.player2, &p2_left]
+                )
+            );
         }
         MatchFormat::SingleRound => {}
     }
+
+    if let Some(outlook) = match_outlook(state) {
+        println!("{}", outlook);
+    }
+    if state.history.len() > 1 {
+        let trend = history_sparkline(&state.history);
+        println!("{}", tf("round.trend", &[&cfg.player1, &trend]));
+    }
+    if let Some(notice) = match_point_notice(state) {
+        println!("{}", color::notice(&notice));
+    }
 }
 
 fn show_victory(state: &MatchState, winner: RoundWinner) {
     let cfg = &state.config;
 
-    println!("Match complete.\n");
+    println!("{}\n", t("victory.complete"));
+    sound::match_end(winner, cfg.mode == Mode::SinglePlayer);
 
-    let final_score = format!(
-        "Final Score: {} {} - {} {}",
-        cfg.player1, state.p1_round_wins, state.p2_round_wins, cfg.player2
+    let (p1_score, p2_score) = displayed_score(state);
+    let final_score = tf(
+        "victory.final_score",
+        &[&cfg.player1, &p1_score, &p2_score, &cfg.player2],
     );
 
-    println!("{}", cyan(&final_score));
+    println!("{}", accent(&final_score));
     println!();
 
-    match winner {
-        RoundWinner::Tie => println!("{}", yellow("It ended in a tie.")),
-        RoundWinner::Player1 => println!("{}", green(&format!("Winner: {}", cfg.player1))),
-        RoundWinner::Player2 => println!("{}", green(&format!("Winner: {}", cfg.player2))),
+    let verdict = match winner {
+        RoundWinner::Tie => t("victory.tie").to_string(),
+        RoundWinner::Player1 => tf("victory.winner", &[&cfg.player1]),
+        RoundWinner::Player2 => tf("victory.winner", &[&cfg.player2]),
+    };
+    println!("{}", result_line(cfg, winner, &verdict));
+    let conceded_by = match state.conceded_to {
+        Some(RoundWinner::Player1) => Some(&cfg.player2),
+        Some(RoundWinner::Player2) => Some(&cfg.player1),
+        _ => None,
+    };
+    if let Some(name) = conceded_by {
+        println!("{}", tf("victory.conceded", &[name]));
+    }
+    if let Some(r) = decisive_round(state) {
+        let (name, other) = match winner {
+            RoundWinner::Player1 => (&cfg.player1, &cfg.player2),
+            _ => (&cfg.player2, &cfg.player1),
+        };
+        let (own, theirs) = match winner {
+            RoundWinner::Player1 => (r.p1_move, r.p2_move),
+            _ => (r.p2_move, r.p1_move),
+        };
+        let own = move_name(&cfg.ruleset, own);
+        let theirs = move_name(&cfg.ruleset, theirs);
+        // In a points match a tie can be what carries the winner over.
+        let key = if r.winner == RoundWinner::Tie {
+            "victory.decided_tie"
+        } else {
+            "victory.decided"
+        };
+        println!("{}", tf(key, &[name, &r.round, &own, other, &theirs]));
+    }
+    if let Some(secs) = state.duration_secs() {
+        println!("{}", tf("victory.duration", &[&format_duration(secs)]));
+    }
+    if let Some(d) = cfg.difficulty.filter(|_| !cfg.reveal_difficulty) {
+        let name = lang::difficulty_name(d);
+        println!("{}", tf("victory.difficulty", &[&cfg.player2, &name]));
+    }
+
+    if let Some((hits, total)) = prediction_accuracy(state) {
+        let percent = format!("{:.0}", hits as f32 / total as f32 * 100.0);
+        println!(
+            "{}",
+            tf(
+                "victory.predicted",
+                &[&cfg.player2, &hits, &total, &percent]
+            )
+        );
     }
 
     if let Some(last) = state.history.last() {
+        let rules = &cfg.ruleset;
         println!();
-        println!("{} chose: {}", cfg.player1, last.p1_move.name());
-        println!("{}", ascii_move(last.p1_move));
+        println!(
+            "{}",
+            tf(
+                "match.chose",
+                &[&cfg.player1, &move_name(rules, last.p1_move)]
+            )
+        );
+        print_move_art(cfg, last.p1_move);
 
-        println!("{} chose: {}", cfg.player2, last.p2_move.name());
-        println!("{}", ascii_move(last.p2_move));
+        println!(
+            "{}",
+            tf(
+                "match.chose",
+                &[&cfg.player2, &move_name(rules, last.p2_move)]
+            )
+        );
+        print_move_art(cfg, last.p2_move);
     }
 }
 
-fn check_match_winner(state: &MatchState) -> Option<RoundWinner> {
-    match state.config.format {
-        MatchFormat::SingleRound => state.history.last().map(|r| r.winner),
-        MatchFormat::BestOfN(n) => {
-            let needed = n / 2 + 1;
-            if state.p1_round_wins >= needed {
-                Some(RoundWinner::Player1)
-            } else if state.p2_round_wins >= needed {
-                Some(RoundWinner::Player2)
-            } else {
-                None
-            }
-        }
-        MatchFormat::FirstToK(k) => {
-            if state.p1_round_wins >= k {
-                Some(RoundWinner::Player1)
-            } else if state.p2_round_wins >= k {
-                Some(RoundWinner::Player2)
-            } else {
-                None
-            }
+fn print_move_histogram(state: &MatchState) {
+    let cfg = &state.config;
+    if !graphics_enabled(cfg) || state.history.is_empty() {
+        return;
+    }
+
+    let moves = Move::all_for_ruleset(&cfg.ruleset);
+    let count = |pick: fn(&RoundRecord) -> Move, mv: Move| {
+        state.history.iter().filter(|r| pick(r) == mv).count()
+    };
+    let p1_counts: Vec<usize> = moves.iter().map(|&m| count(|r| r.p1_move, m)).collect();
+    let p2_counts: Vec<usize> = moves.iter().map(|&m| count(|r| r.p2_move, m)).collect();
+    let max = p1_counts
+        .iter()
+        .chain(&p2_counts)
+        .copied()
+        .max()
+        .unwrap_or(0);
+    let label_width = moves
+        .iter()
+        .map(|&m| move_name(&cfg.ruleset, m).chars().count())
+        .max()
+        .unwrap_or(0);
+
+    println!("\n{}", t("histogram.title"));
+    for (name, counts) in [(&cfg.player1, &p1_counts), (&cfg.player2, &p2_counts)] {
+        println!("\n{}", name);
+        for (&mv, &n) in moves.iter().zip(counts) {
+            // Padded by hand: the color codes would throw off {:<width$}.
+            let len = n * HISTOGRAM_WIDTH / max.max(1);
+            println!(
+                "  {:<width$}  {}{}  {}",
+                move_name(&cfg.ruleset, mv),
+                accent(&"#".repeat(len)),
+                " ".repeat(HISTOGRAM_WIDTH - len),
+                n,
+                width = label_width
+            );
         }
     }
 }
 
-fn accepted_inputs_line(ruleset: Ruleset) -> &'static str {
-    match ruleset {
-        Ruleset::Classic => "rock / paper / scissors  OR  r / p / s",
-        Ruleset::Extended => "rock / paper / scissors / lizard / spock  OR  r / p / s / l / k",
-    }
+// Input is parsed the same in every language, so these stay in English.
+fn accepted_inputs_line(ruleset: &Ruleset) -> String {
+    let (names, shortcuts) = match ruleset {
+        Ruleset::Classic => (
+            "rock / paper / scissors".to_string(),
+            "r / p / s".to_string(),
+        ),
+        Ruleset::Extended => (
+            "rock / paper / scissors / lizard / spock".to_string(),
+            "r / p / s / l / k".to_string(),
+        ),
+        Ruleset::Custom(c) => {
+            let names: Vec<String> = c.moves.iter().map(|m| m.to_lowercase()).collect();
+            let numbers: Vec<String> = (1..=c.moves.len()).map(|i| i.to_string()).collect();
+            (names.join(" / "), numbers.join(" / "))
+        }
+    };
+    format!("{}  {}  {}", names, t("match.or"), shortcuts)
 }
 
-enum MoveOrSave {
+// Save, Quit and History are typed at the prompt in place of a move.
+enum MoveInput {
     Move(Move),
     Save,
+    Quit,
+    History,
+    Rules,
+}
+
+// What the match loop does when a move prompt got a command instead.
+enum Command {
+    Leave,
+    Reprompt,
+}
+
+fn move_aliases() -> &'static MoveAliases {
+    static ALIASES: OnceLock<MoveAliases> = OnceLock::new();
+    ALIASES.get_or_init(|| {
+        let mut aliases = lang::move_aliases();
+        aliases.extend(load_aliases());
+        aliases
+    })
 }
 
-fn parse_move_or_save(input: &str, ruleset: Ruleset) -> Option<MoveOrSave> {
-    let t = input.trim().to_lowercase();
-    if t == "save" || t == "sv" {
-        return Some(MoveOrSave::Save);
+// Prints why the input was rejected and returns None so the caller asks
+// again. With hidden input the candidates aren't named, since they'd give
+// the move away to anyone watching.
+fn parse_move_input(
+    input: &str,
+    ruleset: &Ruleset,
+    allow_save: bool,
+    hidden: bool,
+) -> Option<MoveInput> {
+    let typed = input.trim().to_lowercase();
+    if allow_save && (typed == "save" || typed == "sv") {
+        return Some(MoveInput::Save);
+    }
+    match typed.as_str() {
+        ":q" | "quit" => return Some(MoveInput::Quit),
+        ":h" | "history" => return Some(MoveInput::History),
+        ":r" | "rules" => return Some(MoveInput::Rules),
+        _ => {}
+    }
+    match match_move(&typed, ruleset, move_aliases()) {
+        MoveMatch::Found(mv) => return Some(MoveInput::Move(mv)),
+        MoveMatch::Ambiguous(_) if hidden => println!("{}", t("match.ambiguous_hidden")),
+        MoveMatch::Ambiguous(options) => {
+            let names: Vec<&str> = options.iter().map(|&m| move_name(ruleset, m)).collect();
+            println!("{}", tf("match.ambiguous", &[&names.join(" / ")]));
+        }
+        MoveMatch::NotFound => println!("{}", t("match.invalid_move")),
     }
-    parse_move(&t, ruleset).map(MoveOrSave::Move)
+    None
 }
 
-fn read_move_player_or_save(player_name: &str, ruleset: Ruleset, allow_save: bool) -> MoveOrSave {
-    loop {
-        let s = read_line(&format!("{} move: ", player_name));
-        if allow_save {
-            if let Some(v) = parse_move_or_save(&s, ruleset) {
-                return v;
-            }
-        } else if let Some(mv) = parse_move(&s, ruleset) {
-            return MoveOrSave::Move(mv);
+// A scripted move is echoed after the prompt, unless it's hidden input. A
+// line that isn't a move ends the program: carrying on would put every
+// later line at the wrong prompt.
+fn scripted_move(prompt: Option<&str>, ruleset: &Ruleset, allow_save: bool) -> Option<MoveInput> {
+    let line = input::next_script_line()?;
+    if let Some(prompt) = prompt {
+        println!("{}{}", prompt, line.text);
+    }
+    match parse_move_input(&line.text, ruleset, allow_save, prompt.is_none()) {
+        Some(v) => Some(v),
+        None => {
+            eprintln!(
+                "rps: {} line {}: \"{}\" isn't a move here",
+                line.path, line.number, line.text
+            );
+            std::process::exit(2);
         }
-        println!("Invalid move.");
     }
 }
 
-fn read_move_hidden_or_save(_player_name: &str, ruleset: Ruleset, allow_save: bool) -> MoveOrSave {
+// None means a line should be read instead: single-key entry is off, or the
+// player pressed Enter to type a command or a longer name.
+fn read_move_key(prompt: &str, ruleset: &Ruleset, hidden: bool) -> Option<MoveInput> {
+    if !input::single_key() {
+        return None;
+    }
+    println!("{}", t("match.key_hint"));
     loop {
-        let s = read_password().unwrap_or_default();
-        if allow_save {
-            if let Some(v) = parse_move_or_save(&s, ruleset) {
-                return v;
-            }
-        } else if let Some(mv) = parse_move(&s, ruleset) {
-            return MoveOrSave::Move(mv);
+        print!("{}", prompt);
+        let _ = io::stdout().flush();
+        let key = input::key()?;
+        if key == '\n' || key == '\r' {
+            println!();
+            return None;
+        }
+        if hidden {
+            println!();
+        } else {
+            println!("{}", key);
+        }
+        if let Some(v) = parse_move_input(&key.to_string(), ruleset, false, hidden) {
+            return Some(v);
         }
-        println!("Invalid move.");
     }
 }
 
-fn parse_move(input: &str, ruleset: Ruleset) -> Option<Move> {
-    match input.trim().to_lowercase().as_str() {
-        "rock" | "r" => Some(Move::Rock),
-        "paper" | "p" => Some(Move::Paper),
-        "scissors" | "s" => Some(Move::Scissors),
-        "lizard" | "l" if matches!(ruleset, Ruleset::Extended) => Some(Move::Lizard),
-        "spock" | "k" if matches!(ruleset, Ruleset::Extended) => Some(Move::Spock),
-        _ => None,
+// Returns None if the timeout runs out. The clock covers every attempt at
+// this prompt: retyping after an invalid move doesn't reset it. It starts
+// here, so menus, the history view and pauses before the prompt are free.
+fn read_move_player(
+    player_name: &str,
+    ruleset: &Ruleset,
+    allow_save: bool,
+    timeout: Option<Duration>,
+) -> Option<MoveInput> {
+    let prompt = tf("match.move_prompt", &[&player_name]);
+    if let Some(v) = scripted_move(Some(&prompt), ruleset, allow_save) {
+        return Some(v);
+    }
+    // A keypress can't be waited for with a time limit.
+    if timeout.is_none() {
+        if let Some(v) = read_move_key(&prompt, ruleset, false) {
+            return Some(v);
+        }
+    }
+    let deadline = timeout.map(|t| Instant::now() + t);
+    loop {
+        let s = match deadline {
+            None => read_line(&prompt),
+            Some(deadline) => {
+                print!("{}", prompt);
+                let _ = io::stdout().flush();
+                let left = deadline.saturating_duration_since(Instant::now());
+                match input::line_within(left) {
+                    input::Timed::Line(Some(line)) => line.trim().to_string(),
+                    input::Timed::Line(None) => interrupt::input_closed(),
+                    input::Timed::TimedOut => return None,
+                }
+            }
+        };
+        if let Some(v) = parse_move_input(&s, ruleset, allow_save, false) {
+            return Some(v);
+        }
     }
 }
 
-fn decide_winner(ruleset: Ruleset, p1: Move, p2: Move) -> RoundWinner {
-    if p1 == p2 {
-        return RoundWinner::Tie;
+fn read_move_hidden(prompt: &str, ruleset: &Ruleset, allow_save: bool) -> MoveInput {
+    if let Some(v) = scripted_move(None, ruleset, allow_save) {
+        return v;
     }
-
-    let p1_wins = match ruleset {
-        Ruleset::Classic => classic_beats(p1, p2),
-        Ruleset::Extended => extended_beats(p1, p2),
-    };
-
-    if p1_wins {
-        RoundWinner::Player1
-    } else {
-        RoundWinner::Player2
+    if let Some(v) = read_move_key(prompt, ruleset, true) {
+        return v;
+    }
+    loop {
+        print!("{}", prompt);
+        let _ = io::stdout().flush();
+        interrupt::set_hidden_input(true);
+        let read = read_password();
+        interrupt::set_hidden_input(false);
+        // Without a terminal to read from this fails every time.
+        let Ok(s) = read else {
+            interrupt::input_closed();
+        };
+        if let Some(v) = parse_move_input(&s, ruleset, allow_save, true) {
+            return v;
+        }
     }
 }
 
-fn classic_beats(a: Move, b: Move) -> bool {
-    matches!(
-        (a, b),
-        (Move::Rock, Move::Scissors) | (Move::Paper, Move::Rock) | (Move::Scissors, Move::Paper)
-    )
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
 
-fn extended_beats(a: Move, b: Move) -> bool {
-    matches!(
-        (a, b),
-        (Move::Rock, Move::Scissors)
-            | (Move::Rock, Move::Lizard)
-            | (Move::Paper, Move::Rock)
-            | (Move::Paper, Move::Spock)
-            | (Move::Scissors, Move::Paper)
-            | (Move::Scissors, Move::Lizard)
-            | (Move::Lizard, Move::Spock)
-            | (Move::Lizard, Move::Paper)
-            | (Move::Spock, Move::Scissors)
-            | (Move::Spock, Move::Rock)
-    )
-}
+    // Running out means the test's script is wrong; input_closed would exit
+    // the test binary instead of failing.
+    struct Script(VecDeque<String>);
 
-fn ai_move(state: &mut MatchState, human_move: Move) -> Move {
-    state.human_recent.push(human_move);
-    if state.human_recent.len() > 12 {
-        state.human_recent.remove(0);
+    impl input::LineSource for Script {
+        fn next_line(&mut self) -> Option<String> {
+            let line = self.0.pop_front().expect("the game asked for more input");
+            Some(format!("{}\n", line))
+        }
     }
 
-    let rules = state.config.ruleset;
-    let all = Move::all_for_ruleset(rules);
-    let diff = state.config.difficulty.unwrap_or(Difficulty::Easy);
+    #[derive(Clone)]
+    struct Capture(Arc<Mutex<String>>);
 
-    match diff {
-        Difficulty::Easy => random_from(&all),
-        Difficulty::Normal => {
-            let roll: u8 = rand::thread_rng().gen_range(0..100);
-            if roll < 65 {
-                random_from(&all)
-            } else {
-                best_counter(rules, human_move)
-            }
-        }
-        Difficulty::Hard => {
-            let predicted = most_common(&state.human_recent).unwrap_or(human_move);
-            best_counter(rules, predicted)
+    impl output::Output for Capture {
+        fn write_str(&mut self, s: &str) {
+            self.0.lock().unwrap().push_str(s);
         }
     }
-}
-
-fn random_from(list: &[Move]) -> Move {
-    let idx = rand::thread_rng().gen_range(0..list.len());
-    list[idx]
-}
 
-fn most_common(list: &[Move]) -> Option<Move> {
-    let mut freq: HashMap<Move, usize> = HashMap::new();
-    for &m in list {
-        *freq.entry(m).or_insert(0) += 1;
+    fn drive(lines: &[&str]) -> Capture {
+        let lines = lines.iter().map(|l| l.to_string()).collect();
+        input::set_source(Box::new(Script(lines)));
+        let capture = Capture(Arc::new(Mutex::new(String::new())));
+        output::set_sink(Box::new(capture.clone()));
+        capture
     }
-    freq.into_iter().max_by_key(|(_, c)| *c).map(|(m, _)| m)
-}
 
-fn best_counter(ruleset: Ruleset, target: Move) -> Move {
-    let candidates: Vec<Move> = Move::all_for_ruleset(ruleset)
-        .into_iter()
-        .filter(|&m| match ruleset {
-            Ruleset::Classic => classic_beats(m, target),
-            Ruleset::Extended => extended_beats(m, target),
-        })
-        .collect();
+    #[test]
+    fn scripted_best_of_three_is_played_and_recorded() {
+        // The scoreboard is saved to the working directory.
+        let dir = std::env::temp_dir().join(format!("rps-e2e-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        std::env::set_current_dir(&dir).unwrap();
+
+        let args = [
+            "--p1",
+            "Al",
+            "--format",
+            "bestof:3",
+            "--no-color",
+            "--no-ascii",
+        ];
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        let cli::Launch::Match(mut config) = cli::parse_args(&args).unwrap() else {
+            panic!("expected a match");
+        };
+        // With this seed Easy plays Rock, Scissors, Rock.
+        config.rng_seed = Some(7);
+        let mut state = MatchState::new(config);
+        let mut scoreboard = Scoreboard::default();
+
+        // Start, then Paper each round with "next round" in between, then
+        // back to the menu.
+        let capture = drive(&["1", "p", "1", "p", "1", "p", "5"]);
+        run_match(&mut state, &mut scoreboard);
+
+        assert_eq!((state.p1_round_wins, state.p2_round_wins), (2, 1));
+        assert_eq!(state.history.len(), 3);
+        assert_eq!(check_match_winner(&state), Some(RoundWinner::Player1));
+
+        let out = capture.0.lock().unwrap().clone();
+        let final_score = tf(
+            "victory.final_score",
+            &[&"Al", &2, &1, &computer_name(Difficulty::Easy, true)],
+        );
+        assert!(out.contains(&final_score), "{}", out);
+
+        let saved = Scoreboard::load_from(SCORE_FILE).unwrap();
+        for sb in [&scoreboard, &saved] {
+            let al = sb.player("Al").unwrap();
+            assert_eq!(
+                (al.matches_played, al.matches_won, al.rounds_won),
+                (1, 1, 2)
+            );
+            let computer = sb.player(&state.config.player2).unwrap();
+            assert_eq!(
+                (
+                    computer.matches_played,
+                    computer.matches_won,
+                    computer.rounds_won
+                ),
+                (1, 0, 1)
+            );
+            assert!(al.elo > computer.elo);
+        }
 
-    if candidates.is_empty() {
-        target
-    } else {
-        random_from(&candidates)
+        let _ = fs::remove_dir_all(&dir);
     }
 }
@@ -0,0 +1,173 @@
+use crate::lang::{t, tf};
+use rps_game::{commitment, new_nonce, verify_commitment, GameConfig, MatchState, Move};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+// Nothing we send comes close; a bigger prefix means the peer isn't us.
+const MAX_MESSAGE_LEN: u32 = 1 << 20;
+
+// Every message is a big-endian u32 length followed by this much JSON.
+#[derive(Serialize, Deserialize)]
+enum Message {
+    Hello { name: String },
+    Start(Box<MatchState>),
+    Commit { hash: String },
+    Reveal { mv: Move, nonce: String },
+    Bye,
+}
+
+// Kept out of MatchState, which is cloned for the interrupt handler and
+// written to save files.
+static PEER: Mutex<Option<TcpStream>> = Mutex::new(None);
+static LOCAL_IS_P1: AtomicBool = AtomicBool::new(true);
+
+fn send(stream: &mut TcpStream, msg: &Message) -> Result<(), String> {
+    let body = serde_json::to_vec(msg).map_err(|e| e.to_string())?;
+    stream
+        .write_all(&(body.len() as u32).to_be_bytes())
+        .and_then(|_| stream.write_all(&body))
+        .map_err(|e| e.to_string())
+}
+
+fn receive(stream: &mut TcpStream) -> Result<Message, String> {
+    let mut len = [0u8; 4];
+    stream.read_exact(&mut len).map_err(|e| e.to_string())?;
+    let len = u32::from_be_bytes(len);
+    if len > MAX_MESSAGE_LEN {
+        return Err(t("network.bad_message").to_string());
+    }
+    let mut body = vec![0u8; len as usize];
+    stream.read_exact(&mut body).map_err(|e| e.to_string())?;
+    match serde_json::from_slice(&body) {
+        Ok(Message::Bye) => Err(t("network.peer_left").to_string()),
+        Ok(msg) => Ok(msg),
+        Err(_) => Err(t("network.bad_message").to_string()),
+    }
+}
+
+// ":4000" listens on every interface.
+pub fn host(addr: &str, mut state: MatchState) -> Result<MatchState, String> {
+    let addr = if addr.starts_with(':') {
+        format!("0.0.0.0{}", addr)
+    } else {
+        addr.to_string()
+    };
+    let listener = TcpListener::bind(&addr).map_err(|e| tf("network.bind_failed", &[&addr, &e]))?;
+    println!("{}", tf("network.listening", &[&addr]));
+
+    let (mut stream, peer) = listener.accept().map_err(|e| e.to_string())?;
+    let guest = match receive(&mut stream)? {
+        Message::Hello { name } => name,
+        _ => return Err(t("network.bad_message").to_string()),
+    };
+
+    // A new match leaves player2 for whoever connects; a resumed one already
+    // names both sides and the guest has to be one of them.
+    let cfg = &mut state.config;
+    let local_is_p1 = if cfg.player2.is_empty() {
+        if guest.is_empty() || guest == cfg.player1 {
+            let _ = send(&mut stream, &Message::Bye);
+            return Err(tf("network.name_taken", &[&guest]));
+        }
+        cfg.player2 = guest;
+        true
+    } else if guest == cfg.player2 {
+        true
+    } else if guest == cfg.player1 {
+        false
+    } else {
+        let _ = send(&mut stream, &Message::Bye);
+        return Err(tf("network.not_in_save", &[&guest]));
+    };
+
+    send(&mut stream, &Message::Start(Box::new(state.clone())))?;
+    println!("{}", tf("network.connected", &[&peer]));
+    connected(stream, local_is_p1);
+    Ok(state)
+}
+
+// The host decides the match; only the display settings stay local.
+pub fn connect(addr: &str, local: &GameConfig) -> Result<MatchState, String> {
+    let mut stream =
+        TcpStream::connect(addr).map_err(|e| tf("network.connect_failed", &[&addr, &e]))?;
+    send(
+        &mut stream,
+        &Message::Hello {
+            name: local.player1.clone(),
+        },
+    )?;
+    let mut state = match receive(&mut stream)? {
+        Message::Start(state) => *state,
+        _ => return Err(t("network.bad_message").to_string()),
+    };
+
+    state.config.use_color = local.use_color;
+    state.config.show_ascii = local.show_ascii;
+    state.config.show_emoji = local.show_emoji;
+    state.config.event_log = local.event_log.clone();
+    let local_is_p1 = state.config.player1 == local.player1;
+    connected(stream, local_is_p1);
+    Ok(state)
+}
+
+fn connected(stream: TcpStream, local_is_p1: bool) {
+    LOCAL_IS_P1.store(local_is_p1, Ordering::SeqCst);
+    if let Ok(mut peer) = PEER.lock() {
+        *peer = Some(stream);
+    }
+}
+
+pub fn local_is_p1() -> bool {
+    LOCAL_IS_P1.load(Ordering::SeqCst)
+}
+
+// Both sides send a hash of their move before either reveals it, so seeing
+// the other move first doesn't help. Any failure drops the connection.
+pub fn exchange(own: Move, moves: &[Move]) -> Result<Move, String> {
+    let mut peer = PEER.lock().map_err(|e| e.to_string())?;
+    let stream = peer
+        .as_mut()
+        .ok_or_else(|| t("network.not_connected").to_string())?;
+
+    let result = (|| {
+        let nonce = new_nonce(&mut rand::thread_rng());
+        send(
+            stream,
+            &Message::Commit {
+                hash: commitment(own, &nonce),
+            },
+        )?;
+        let hash = match receive(stream)? {
+            Message::Commit { hash } => hash,
+            _ => return Err(t("network.bad_message").to_string()),
+        };
+
+        send(stream, &Message::Reveal { mv: own, nonce })?;
+        let (theirs, nonce) = match receive(stream)? {
+            Message::Reveal { mv, nonce } => (mv, nonce),
+            _ => return Err(t("network.bad_message").to_string()),
+        };
+
+        if !verify_commitment(theirs, &nonce, &hash) || !moves.contains(&theirs) {
+            return Err(t("network.bad_reveal").to_string());
+        }
+        Ok(theirs)
+    })();
+
+    if result.is_err() {
+        *peer = None;
+    }
+    result
+}
+
+pub fn disconnect() {
+    if let Ok(mut peer) = PEER.lock() {
+        if let Some(mut stream) = peer.take() {
+            let _ = send(&mut stream, &Message::Bye);
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::Mutex;
+
+// Where the print! and println! at the top of main.rs send everything:
+// stdout, unless a test has put something else in its place.
+pub trait Output: Send {
+    fn write_str(&mut self, s: &str);
+}
+
+static SINK: Mutex<Option<Box<dyn Output>>> = Mutex::new(None);
+
+#[cfg(test)]
+pub fn set_sink(sink: Box<dyn Output>) {
+    *SINK.lock().unwrap_or_else(|e| e.into_inner()) = Some(sink);
+}
+
+// A closed stdout isn't worth a panic in the middle of a match, so write
+// errors are dropped.
+pub fn print(args: fmt::Arguments) {
+    let mut sink = SINK.lock().unwrap_or_else(|e| e.into_inner());
+    match sink.as_mut() {
+        Some(sink) => sink.write_str(&args.to_string()),
+        None => {
+            let _ = io::stdout().lock().write_fmt(args);
+        }
+    }
+}
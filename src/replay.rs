@@ -0,0 +1,249 @@
+use crate::lang::{t, tf};
+use crate::{clear_screen, menu_item, pause, print_round_summary, read_line, read_menu_choice};
+use rps_game::{
+    apply_round, displayed_score, list_save_slots, load_saved_game, migrate_legacy_save,
+    CustomRuleset, GameConfig, MatchState, Mode, Move, RoundRecord, RoundWinner, Ruleset,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::thread;
+use std::time::Duration;
+
+const AUTO_ADVANCE_MS: u64 = 1500;
+
+enum Source {
+    Slot(u32),
+    Csv(String),
+}
+
+pub fn choose_replay() -> Option<MatchState> {
+    migrate_legacy_save();
+
+    let mut sources: Vec<(Source, String)> = Vec::new();
+    for slot in list_save_slots() {
+        if let Ok(st) = load_saved_game(slot) {
+            let label = tf(
+                "replay.slot",
+                &[
+                    &slot,
+                    &st.config.player1,
+                    &st.config.player2,
+                    &st.history.len(),
+                ],
+            );
+            sources.push((Source::Slot(slot), label));
+        }
+    }
+    for path in list_csv_exports() {
+        let label = tf("replay.export", &[&path]);
+        sources.push((Source::Csv(path), label));
+    }
+
+    if sources.is_empty() {
+        println!("\n{}", t("replay.none"));
+        pause();
+        return None;
+    }
+
+    clear_screen();
+    println!("{}\n", t("replay.title"));
+    for (i, (_, label)) in sources.iter().enumerate() {
+        menu_item(i + 1, label);
+    }
+    let back = sources.len() as i32 + 1;
+    menu_item(back, t("common.back"));
+
+    let choice = read_menu_choice(1, back);
+    if choice == back {
+        return None;
+    }
+
+    let loaded = match &sources[choice as usize - 1].0 {
+        Source::Slot(slot) => load_saved_game(*slot),
+        Source::Csv(path) => load_match_csv(path),
+    };
+    match loaded {
+        Ok(state) if !state.history.is_empty() => Some(state),
+        Ok(_) => {
+            println!("\n{}", t("replay.no_rounds"));
+            pause();
+            None
+        }
+        Err(e) => {
+            println!("\n{}", e);
+            pause();
+            None
+        }
+    }
+}
+
+pub fn replay_match(state: &MatchState) {
+    println!();
+    menu_item(1, t("replay.manual"));
+    menu_item(2, t("replay.auto"));
+    let auto = read_menu_choice(1, 2) == 2;
+
+    let mut view = MatchState::new(state.config.clone());
+    let total = state.history.len();
+    for (i, r) in state.history.iter().enumerate() {
+        view.round_number = r.round;
+        apply_round(&mut view, r.p1_move, r.p2_move, r.winner);
+
+        clear_screen();
+        println!("{}\n", tf("replay.round", &[&(i + 1), &total]));
+        print_round_summary(&view, r.p1_move, r.p2_move, r.winner);
+
+        if auto {
+            thread::sleep(Duration::from_millis(AUTO_ADVANCE_MS));
+        } else if i + 1 < total {
+            read_line(&format!("\n{}", t("replay.next")));
+        }
+    }
+
+    let (p1_score, p2_score) = displayed_score(&view);
+    let end = tf(
+        "replay.end",
+        &[
+            &view.config.player1,
+            &p1_score,
+            &p2_score,
+            &view.config.player2,
+        ],
+    );
+    println!("\n{}", end);
+    pause();
+}
+
+fn list_csv_exports() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(".") else {
+        return vec![];
+    };
+    let mut paths: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.file_name().into_string().ok())
+        .filter(|name| name.starts_with("rps_") && name.ends_with(".csv"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+// Exports only carry move names and running totals, so the players come from
+// the file name and the ruleset is guessed from the moves seen. Round winners
+// are read off the totals rather than re-decided.
+fn load_match_csv(path: &str) -> Result<MatchState, String> {
+    let data = fs::read_to_string(path).map_err(|e| tf("common.read_failed", &[&path, &e]))?;
+
+    let (player1, player2) = path
+        .strip_prefix("rps_")
+        .and_then(|s| s.strip_suffix(".csv"))
+        .and_then(|s| s.rsplit_once('_'))
+        .and_then(|(names, _stamp)| names.split_once("_vs_"))
+        .map(|(a, b)| (a.to_string(), b.to_string()))
+        .unwrap_or_else(|| ("Player 1".to_string(), "Player 2".to_string()));
+
+    let mut rows = Vec::new();
+    for (n, line) in data.lines().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields = split_csv_line(line);
+        if fields.len() != 6 {
+            return Err(tf("replay.bad_fields", &[&path, &(n + 1)]));
+        }
+        let num = |i: usize| {
+            fields[i]
+                .parse::<u32>()
+                .map_err(|_| tf("replay.bad_number", &[&path, &(n + 1)]))
+        };
+        rows.push((
+            num(0)?,
+            fields[1].clone(),
+            fields[2].clone(),
+            num(4)?,
+            num(5)?,
+        ));
+    }
+
+    let names: Vec<&str> = rows
+        .iter()
+        .flat_map(|(_, a, b, _, _)| [a.as_str(), b.as_str()])
+        .collect();
+    let builtin = |rules: &Ruleset| {
+        let known = Move::all_for_ruleset(rules);
+        names.iter().all(|n| known.iter().any(|m| m.name() == *n))
+    };
+    let ruleset = if builtin(&Ruleset::Classic) {
+        Ruleset::Classic
+    } else if builtin(&Ruleset::Extended) {
+        Ruleset::Extended
+    } else {
+        let mut moves: Vec<String> = Vec::new();
+        for n in &names {
+            if !moves.iter().any(|m| m == n) {
+                moves.push(n.to_string());
+            }
+        }
+        Ruleset::Custom(CustomRuleset {
+            name: "Custom".to_string(),
+            moves,
+            beats: HashMap::new(),
+        })
+    };
+    let to_move = |name: &str| match &ruleset {
+        Ruleset::Custom(c) => Move::Custom(c.index_of(name).unwrap_or_default() as u8),
+        rules => Move::all_for_ruleset(rules)
+            .into_iter()
+            .find(|m| m.name() == name)
+            .unwrap_or(Move::Rock),
+    };
+
+    let mut history = Vec::new();
+    let (mut p1_prev, mut p2_prev) = (0, 0);
+    for (round, a, b, p1_total, p2_total) in &rows {
+        let winner = if *p1_total > p1_prev {
+            RoundWinner::Player1
+        } else if *p2_total > p2_prev {
+            RoundWinner::Player2
+        } else {
+            RoundWinner::Tie
+        };
+        (p1_prev, p2_prev) = (*p1_total, *p2_total);
+        history.push(RoundRecord {
+            round: *round,
+            p1_move: to_move(a),
+            p2_move: to_move(b),
+            winner,
+            tiebreaker: false,
+        });
+    }
+
+    // The format isn't exported either; a single round shows no "to go" line.
+    let config = GameConfig {
+        player1,
+        player2,
+        mode: Mode::Multiplayer,
+        ruleset,
+        ..Default::default()
+    };
+    let mut state = MatchState::new(config);
+    state.history = history;
+    Ok(state)
+}
+
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
@@ -0,0 +1,79 @@
+use crate::color::{accent, win};
+use crate::lang::{t, tf};
+use crate::{clear_screen, color, pause, print_heading, read_line, run_match, should_use_color};
+use rps_game::{check_match_winner, GameConfig, MatchState, RoundWinner, Scoreboard, Series};
+
+pub fn run_series(config: GameConfig, scoreboard: &mut Scoreboard) {
+    let target = loop {
+        let s = read_line(&format!("\n{}", t("series.target")));
+        match s.parse::<u32>() {
+            Ok(v) if v >= 1 => break v,
+            _ => println!("{}", t("common.invalid")),
+        }
+    };
+
+    let mut series = Series::new(target);
+    let winner = loop {
+        // A seeded series would otherwise play the same match every time.
+        let mut match_config = config.clone();
+        match_config.rng_seed = config
+            .rng_seed
+            .map(|s| s.wrapping_add(series.played() as u64));
+        let mut state = MatchState::new(match_config);
+        state.in_series = true;
+
+        run_match(&mut state, scoreboard);
+
+        let Some(match_winner) = check_match_winner(&state) else {
+            clear_screen();
+            println!("{}", tf("series.left_early", &[&series.played()]));
+            pause();
+            return;
+        };
+        series.record(match_winner);
+        if let Some(winner) = series.winner() {
+            break winner;
+        }
+
+        color::set_enabled(should_use_color(&config));
+        clear_screen();
+        print_heading(t("series.title"));
+        print_standing(&config, &series);
+        read_line(&format!("\n{}", t("common.enter_continue")));
+        color::set_enabled(true);
+    };
+
+    if config.ranked {
+        scoreboard.record_series(&config.player1, &config.player2, winner);
+        scoreboard.save();
+    }
+
+    color::set_enabled(should_use_color(&config));
+    clear_screen();
+    print_heading(t("series.title"));
+    let (name, own, theirs) = match winner {
+        RoundWinner::Player2 => (&config.player2, series.p2_wins, series.p1_wins),
+        _ => (&config.player1, series.p1_wins, series.p2_wins),
+    };
+    println!("{}\n", win(&tf("series.winner", &[name, &own, &theirs])));
+    print_standing(&config, &series);
+    pause();
+    color::set_enabled(true);
+}
+
+fn print_standing(config: &GameConfig, series: &Series) {
+    let standing = tf(
+        "series.standing",
+        &[
+            &config.player1,
+            &series.p1_wins,
+            &series.p2_wins,
+            &config.player2,
+            &series.target,
+        ],
+    );
+    println!("{}", accent(&standing));
+    if series.ties > 0 {
+        println!("{}", tf("series.ties", &[&series.ties]));
+    }
+}
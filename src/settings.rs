@@ -0,0 +1,131 @@
+use crate::color;
+use crate::input;
+use crate::lang::{t, tf};
+use crate::sound;
+use crate::{
+    clear_screen, load_move_art, load_theme, menu_item, pause, print_heading, read_graphics,
+    read_line, read_menu_choice, read_yes_no, set_animation_delay, DEFAULT_ANIMATION_MS,
+};
+use rps_game::{
+    Defaults, ART_DIR, DEFAULT_AI_MEMORY, DEFAULT_MIN_MATCHES_FOR_RANKING, MAX_AI_MEMORY,
+};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+// Colors and graphics are the defaults new-game setup offers; the rest
+// aren't asked for anywhere else.
+pub fn settings_menu(defaults: &mut Defaults) {
+    loop {
+        clear_screen();
+        print_heading(t("settings.title"));
+
+        let colors = match defaults.use_color {
+            Some(true) => "y",
+            Some(false) => "n",
+            None => t("settings.ask"),
+        };
+        let graphics = match defaults.show_ascii.zip(defaults.show_emoji) {
+            Some((_, true)) => t("graphics.emoji"),
+            Some((true, false)) => t("graphics.ascii"),
+            Some((false, false)) => t("graphics.none"),
+            None => t("settings.ask"),
+        };
+        let animation = defaults.animation_ms.unwrap_or(DEFAULT_ANIMATION_MS);
+        let memory = defaults.ai_memory.unwrap_or(DEFAULT_AI_MEMORY);
+        menu_item(1, format!("{}: {}", t("settings.colors"), colors));
+        menu_item(2, format!("{}: {}", t("settings.graphics"), graphics));
+        menu_item(3, format!("{}: {}", t("settings.animation"), animation));
+        let ranking = defaults.ranking_threshold();
+        menu_item(4, format!("{}: {}", t("settings.ranking"), ranking));
+        menu_item(5, format!("{}: {}", t("settings.ai_memory"), memory));
+        let single_key = if defaults.single_key == Some(true) {
+            "y"
+        } else {
+            "n"
+        };
+        menu_item(6, format!("{}: {}", t("settings.single_key"), single_key));
+        let sound = if defaults.sound == Some(true) {
+            "y"
+        } else {
+            "n"
+        };
+        menu_item(7, format!("{}: {}", t("settings.sound"), sound));
+        let art_dir = defaults
+            .art_dir
+            .as_ref()
+            .map_or(ART_DIR.to_string(), |d| d.display().to_string());
+        menu_item(8, format!("{}: {}", t("settings.art_dir"), art_dir));
+        let theme = defaults.theme.as_deref().unwrap_or("default");
+        menu_item(9, format!("{}: {}", t("settings.theme"), theme));
+        menu_item(10, t("common.back"));
+
+        match read_menu_choice(1, 10) {
+            1 => defaults.use_color = Some(read_yes_no(&format!("\n{}", t("setup.colors")))),
+            2 => {
+                let (ascii, emoji) = read_graphics(defaults.show_ascii.zip(defaults.show_emoji));
+                defaults.show_ascii = Some(ascii);
+                defaults.show_emoji = Some(emoji);
+            }
+            3 => {
+                let prompt = tf("settings.animation_prompt", &[&DEFAULT_ANIMATION_MS]);
+                defaults.animation_ms = read_optional(&prompt, |_| true);
+                set_animation_delay(defaults.animation_ms);
+            }
+            4 => {
+                let prompt = tf(
+                    "settings.ranking_prompt",
+                    &[&DEFAULT_MIN_MATCHES_FOR_RANKING],
+                );
+                defaults.min_matches_for_ranking = read_optional(&prompt, |_| true);
+            }
+            5 => {
+                let prompt = tf("setup.ai_memory", &[&MAX_AI_MEMORY, &DEFAULT_AI_MEMORY]);
+                defaults.ai_memory = read_optional(&prompt, |v| (1..=MAX_AI_MEMORY).contains(v));
+            }
+            6 => {
+                let on = read_yes_no(&format!("\n{}", t("settings.single_key_prompt")));
+                defaults.single_key = Some(on);
+                input::set_single_key(on);
+            }
+            7 => {
+                let on = read_yes_no(&format!("\n{}", t("settings.sound_prompt")));
+                defaults.sound = Some(on);
+                sound::set_enabled(on);
+            }
+            8 => {
+                let prompt = tf("settings.art_dir_prompt", &[&ART_DIR]);
+                let dir = read_line(&format!("\n{}", prompt));
+                defaults.art_dir = Some(dir).filter(|d| !d.is_empty()).map(PathBuf::from);
+                load_move_art(defaults);
+            }
+            9 => {
+                println!();
+                let names = color::theme_names();
+                for (i, name) in names.iter().enumerate() {
+                    menu_item(i + 1, name);
+                }
+                let choice = read_menu_choice(1, names.len() as i32);
+                defaults.theme = Some(names[choice as usize - 1].to_string());
+                load_theme(defaults);
+            }
+            _ => return,
+        }
+        defaults.save();
+        println!("{}", t("settings.saved"));
+        pause();
+    }
+}
+
+// Blank goes back to the built-in default.
+fn read_optional<T: FromStr>(prompt: &str, valid: impl Fn(&T) -> bool) -> Option<T> {
+    loop {
+        let s = read_line(&format!("\n{}", prompt));
+        if s.is_empty() {
+            return None;
+        }
+        match s.parse() {
+            Ok(v) if valid(&v) => return Some(v),
+            _ => println!("{}", t("common.invalid")),
+        }
+    }
+}
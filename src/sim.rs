@@ -0,0 +1,96 @@
+use crate::cli::{parse_difficulty, parse_ruleset};
+use rps_game::{
+    apply_round, choose_ai_move, decide_winner, remember_human_move, seed_from_env, Difficulty,
+    GameConfig, MatchState, RoundWinner, Ruleset,
+};
+
+pub const USAGE: &str = "\
+Usage: rps sim [options]
+
+Plays the computer against itself and prints Player 1's win, tie and loss
+percentages, one per line.
+
+  --p1-diff LEVEL        Player 1's difficulty; easy (default), normal, hard,
+                         expert, adaptive or mirror
+  --p2-diff LEVEL        Player 2's difficulty; same choices
+  --rounds N             Rounds to play (default 1000)
+  --ruleset RULES        classic (default), extended, rps:N or a custom
+                         ruleset file";
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut p1 = Difficulty::Easy;
+    let mut p2 = Difficulty::Easy;
+    let mut rounds: u32 = 1000;
+    let mut ruleset = Ruleset::Classic;
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = || {
+            it.next()
+                .cloned()
+                .ok_or_else(|| format!("{} needs a value", arg))
+        };
+        match arg.as_str() {
+            "--p1-diff" => p1 = parse_difficulty(&value()?)?,
+            "--p2-diff" => p2 = parse_difficulty(&value()?)?,
+            "--rounds" => {
+                let v = value()?;
+                match v.parse::<u32>() {
+                    Ok(n) if n >= 1 => rounds = n,
+                    _ => return Err(format!("invalid rounds {}", v)),
+                }
+            }
+            "--ruleset" => ruleset = parse_ruleset(&value()?)?,
+            other => return Err(format!("unknown option {}", other)),
+        }
+    }
+
+    // A seeded run gives the two sides different streams.
+    let seed = seed_from_env();
+    let mut s1 = side(p1, &ruleset, seed);
+    let mut s2 = side(p2, &ruleset, seed.map(|s| s.wrapping_add(1)));
+
+    let (mut wins, mut ties, mut losses) = (0u32, 0u32, 0u32);
+    for _ in 0..rounds {
+        let a = choose_ai_move(&mut s1, None).chosen;
+        let b = choose_ai_move(&mut s2, None).chosen;
+        remember_human_move(&mut s1, b);
+        remember_human_move(&mut s2, a);
+
+        let winner = decide_winner(&ruleset, a, b);
+        let flipped = match winner {
+            RoundWinner::Player1 => RoundWinner::Player2,
+            RoundWinner::Player2 => RoundWinner::Player1,
+            RoundWinner::Tie => RoundWinner::Tie,
+        };
+        apply_round(&mut s1, b, a, flipped);
+        apply_round(&mut s2, a, b, winner);
+
+        match winner {
+            RoundWinner::Player1 => wins += 1,
+            RoundWinner::Player2 => losses += 1,
+            RoundWinner::Tie => ties += 1,
+        }
+    }
+
+    let pct = |n: u32| 100.0 * n as f64 / rounds as f64;
+    println!("win {:.2}", pct(wins));
+    println!("tie {:.2}", pct(ties));
+    println!("loss {:.2}", pct(losses));
+    Ok(())
+}
+
+// Each side gets a match of its own in which the other side is the "human"
+// in the player1 slot.
+fn side(difficulty: Difficulty, ruleset: &Ruleset, seed: Option<u64>) -> MatchState {
+    MatchState::new(GameConfig {
+        player1: "Opponent".to_string(),
+        player2: "Computer".to_string(),
+        ruleset: ruleset.clone(),
+        difficulty: Some(difficulty),
+        rng_seed: seed,
+        use_color: false,
+        show_ascii: false,
+        ..Default::default()
+    })
+}
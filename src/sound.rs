@@ -0,0 +1,43 @@
+use rps_game::RoundWinner;
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// Any value turns the bell off, whatever the settings say.
+const NO_SOUND_ENV: &str = "RPS_NO_SOUND";
+// Terminals run bells sent back to back together into one.
+const BEEP_GAP: Duration = Duration::from_millis(250);
+
+pub fn set_enabled(on: bool) {
+    ENABLED.store(on, Ordering::Relaxed);
+}
+
+fn allowed() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+        && std::env::var_os(NO_SOUND_ENV).is_none()
+        && io::stdout().is_terminal()
+}
+
+// One beep for a win, two for a loss to the computer and three for a tie.
+// Between two people someone always won, so that's one beep too.
+pub fn match_end(winner: RoundWinner, against_computer: bool) {
+    if !allowed() {
+        return;
+    }
+    let beeps = match winner {
+        RoundWinner::Tie => 3,
+        RoundWinner::Player2 if against_computer => 2,
+        _ => 1,
+    };
+    let mut out = io::stdout();
+    for i in 0..beeps {
+        if i > 0 {
+            thread::sleep(BEEP_GAP);
+        }
+        let _ = out.write_all(b"\x07");
+        let _ = out.flush();
+    }
+}
@@ -0,0 +1,179 @@
+use crate::lang::{t, tf};
+use crate::layout;
+use crate::{
+    clear_screen, pause, print_heading, read_graphics, read_line, read_ruleset, read_yes_no,
+    run_match,
+};
+use rps_game::{
+    canonical_name, check_match_winner, seed_from_env, GameConfig, MatchFormat, MatchState, Mode,
+    RoundWinner, Scoreboard, TournamentState,
+};
+
+const NAME_WIDTH: usize = 20;
+// The position column plus the five numbers.
+const STATS_WIDTH: usize = 40;
+
+pub fn run_tournament(scoreboard: &mut Scoreboard) {
+    clear_screen();
+
+    print_heading(t("tournament.title"));
+
+    if TournamentState::load().is_some() && !read_yes_no(t("tournament.replace_saved")) {
+        return;
+    }
+
+    let count = loop {
+        let s = read_line(t("tournament.count"));
+        if let Ok(v) = s.parse::<usize>() {
+            if v >= 3 {
+                break v;
+            }
+        }
+        println!("{}", t("common.invalid"));
+    };
+
+    let mut names: Vec<String> = Vec::with_capacity(count);
+    while names.len() < count {
+        let s = read_line(&tf("tournament.player_name", &[&(names.len() + 1)]));
+        let taken = names
+            .iter()
+            .any(|n| canonical_name(n) == canonical_name(&s));
+        if s.is_empty() || taken {
+            println!("{}", t("tournament.name_invalid"));
+            continue;
+        }
+        names.push(s);
+    }
+
+    println!("\n{}", t("setup.ruleset"));
+    let ruleset = read_ruleset(None);
+
+    let k = loop {
+        let s = read_line(&format!("\n{}", t("tournament.best_of")));
+        if let Ok(v) = s.parse::<u32>() {
+            if v >= 1 {
+                break v;
+            }
+        }
+        println!("{}", t("common.invalid"));
+    };
+
+    let mut tournament = TournamentState::new(names, ruleset, k);
+    println!();
+    tournament.use_color = read_yes_no(t("setup.colors"));
+    (tournament.show_ascii, tournament.show_emoji) = read_graphics(None);
+    tournament.save();
+
+    play_tournament(tournament, scoreboard);
+}
+
+pub fn continue_tournament(scoreboard: &mut Scoreboard) {
+    match TournamentState::load() {
+        Some(tournament) => play_tournament(tournament, scoreboard),
+        None => {
+            println!("\n{}", t("tournament.none_saved"));
+            pause();
+        }
+    }
+}
+
+// Saved after every pairing, so leaving keeps everything played so far.
+fn play_tournament(mut tournament: TournamentState, scoreboard: &mut Scoreboard) {
+    for idx in 0..tournament.pairings.len() {
+        if tournament.completed[idx] {
+            continue;
+        }
+        let (i, j) = tournament.pairings[idx];
+        let config = GameConfig {
+            player1: tournament.players[i].clone(),
+            player2: tournament.players[j].clone(),
+            mode: Mode::Multiplayer,
+            ruleset: tournament.ruleset.clone(),
+            format: MatchFormat::BestOfN(tournament.best_of),
+            rng_seed: seed_from_env(),
+            use_color: tournament.use_color,
+            show_ascii: tournament.show_ascii,
+            show_emoji: tournament.show_emoji,
+            ..Default::default()
+        };
+        let mut state = MatchState::new(config);
+        state.in_tournament = true;
+
+        run_match(&mut state, scoreboard);
+
+        let Some(winner) = check_match_winner(&state) else {
+            break;
+        };
+
+        let standings = &mut tournament.standings;
+        for (player, rounds) in [(i, state.p1_round_wins), (j, state.p2_round_wins)] {
+            standings[player].played += 1;
+            standings[player].rounds_won += rounds;
+        }
+        match winner {
+            RoundWinner::Player1 => {
+                standings[i].wins += 1;
+                standings[j].losses += 1;
+            }
+            RoundWinner::Player2 => {
+                standings[j].wins += 1;
+                standings[i].losses += 1;
+            }
+            RoundWinner::Tie => {
+                standings[i].ties += 1;
+                standings[j].ties += 1;
+            }
+        }
+        tournament.completed[idx] = true;
+        tournament.save();
+    }
+
+    let (completed, total) = (tournament.completed_count(), tournament.pairings.len());
+    if completed == total {
+        TournamentState::clear();
+    }
+
+    let mut standings = tournament.standings;
+    standings.sort_by(|a, b| {
+        b.wins
+            .cmp(&a.wins)
+            .then(b.rounds_won.cmp(&a.rounds_won))
+            .then(a.name.cmp(&b.name))
+    });
+
+    clear_screen();
+    if completed == total {
+        println!("{}\n", t("tournament.final"));
+    } else {
+        let left = tf("tournament.left_early", &[&completed, &total]);
+        println!("{}", left);
+        println!("{}\n", t("tournament.saved"));
+    }
+
+    let name_width = layout::name_width(STATS_WIDTH, NAME_WIDTH);
+    println!(
+        "{:<4} {:<name_width$} {:>6} {:>6} {:>6} {:>6} {:>6}",
+        "#",
+        t("col.player"),
+        t("col.mp"),
+        t("col.w"),
+        t("col.t"),
+        t("col.l"),
+        t("col.rw")
+    );
+    println!("{}", layout::rule('-', name_width + STATS_WIDTH));
+    for (pos, st) in standings.iter().enumerate() {
+        println!(
+            "{:<4} {:<name_width$} {:>6} {:>6} {:>6} {:>6} {:>6}",
+            pos + 1,
+            layout::fit(&st.name, name_width),
+            st.played,
+            st.wins,
+            st.ties,
+            st.losses,
+            st.rounds_won
+        );
+    }
+
+    pause();
+}